@@ -0,0 +1,210 @@
+//! Allocation profiling for `--profile-alloc`. The runner uses a global allocator that
+//! wraps the system allocator and counts allocations while a measurement is running;
+//! otherwise it costs one relaxed atomic load per allocation.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fs;
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering};
+
+pub struct CountingAllocator;
+
+static COUNTING: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+/// Bytes allocated and not freed since the measurement started; negative when memory
+/// from before is freed
+static LIVE: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+
+/// Counts an allocation of `bytes` that changes the memory in use by `change`.
+fn count(bytes: usize, change: isize) {
+    if COUNTING.load(Ordering::Relaxed) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(bytes, Ordering::Relaxed);
+        let live = LIVE.fetch_add(change, Ordering::Relaxed) + change;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            count(layout.size(), layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            count(layout.size(), layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        if COUNTING.load(Ordering::Relaxed) {
+            LIVE.fetch_sub(layout.size() as isize, Ordering::Relaxed);
+        }
+    }
+
+    /// Growing or shrinking a block counts as a new allocation of its new size, as it
+    /// may well be moved.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            count(new_size, new_size as isize - layout.size() as isize);
+        }
+        new_ptr
+    }
+}
+
+/// The allocations of one measured stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Usage {
+    pub allocations: usize,
+    /// Total size of all allocations
+    pub bytes: usize,
+    /// Most memory in use at any time by the allocations of the stage
+    pub peak_heap: usize,
+    /// Peak resident set size of the whole process during the stage, where the OS
+    /// reports it
+    pub peak_rss: Option<usize>,
+}
+
+/// Value of a `VmHWM:`-like line of `/proc/self/status`, in bytes.
+fn status_bytes(status: &str, key: &str) -> Option<usize> {
+    let line = status.lines().find(|line| line.starts_with(key))?;
+    let kib: usize = line[key.len()..].trim().strip_suffix("kB")?.trim().parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// Starts a new peak RSS at the current RSS. Only Linux can do this.
+fn reset_peak_rss() -> bool {
+    fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+fn peak_rss() -> Option<usize> {
+    status_bytes(&fs::read_to_string("/proc/self/status").ok()?, "VmHWM:")
+}
+
+/// Runs `f` and counts the allocations made meanwhile. Allocations of other threads
+/// count too, so only one stage should run at a time.
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, Usage) {
+    let rss_reset = reset_peak_rss();
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    BYTES.store(0, Ordering::Relaxed);
+    LIVE.store(0, Ordering::Relaxed);
+    PEAK.store(0, Ordering::Relaxed);
+    COUNTING.store(true, Ordering::SeqCst);
+    let result = f();
+    COUNTING.store(false, Ordering::SeqCst);
+
+    let usage = Usage {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+        peak_heap: PEAK.load(Ordering::Relaxed).max(0) as usize,
+        peak_rss: if rss_reset { peak_rss() } else { None },
+    };
+    (result, usage)
+}
+
+/// Formats a number of bytes with three significant digits in a readable unit, like
+/// `format_duration`, e.g. "1.50MiB".
+pub fn format_bytes(bytes: usize) -> String {
+    let units = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{}B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < units.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if value < 10.0 {
+        format!("{:.2}{}", value, units[unit])
+    } else if value < 100.0 {
+        format!("{:.1}{}", value, units[unit])
+    } else {
+        format!("{:.0}{}", value, units[unit])
+    }
+}
+
+/// One measured stage of a day: parsing or one of the parts.
+pub struct Row {
+    pub day: u32,
+    pub stage: String,
+    pub usage: Usage,
+}
+
+pub fn format_table(rows: &[Row]) -> String {
+    let mut out = format!(
+        "{:>3}  {:<8}  {:>10}  {:>8}  {:>9}  {:>8}\n",
+        "Day", "Stage", "Allocs", "Bytes", "Peak heap", "Peak RSS"
+    );
+    for row in rows {
+        out += &format!(
+            "{:>3}  {:<8}  {:>10}  {:>8}  {:>9}  {:>8}\n",
+            row.day,
+            row.stage,
+            row.usage.allocations,
+            format_bytes(row.usage.bytes),
+            format_bytes(row.usage.peak_heap),
+            row.usage.peak_rss.map_or_else(|| String::from("-"), format_bytes)
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hint::black_box;
+
+    use super::{Row, Usage};
+
+    #[test]
+    fn measure() {
+        let (len, usage) = super::measure(|| {
+            let mut kept = black_box(vec![0u8; 4000]);
+            for _ in 0..10 {
+                black_box(vec![1u8; 1000]);
+            }
+            kept.push(1);
+            kept.len()
+        });
+        assert_eq!(len, 4001);
+        // Other tests allocate at the same time, so these are lower bounds
+        assert!(usage.allocations >= 12, "{:?}", usage);
+        assert!(usage.bytes >= 4000 + 10 * 1000 + 4001, "{:?}", usage);
+        assert!(usage.peak_heap >= 4001, "{:?}", usage);
+    }
+
+    #[test]
+    fn status_bytes() {
+        let status = "Name:\taoc\nVmPeak:\t   12000 kB\nVmHWM:\t    2048 kB\n";
+        assert_eq!(super::status_bytes(status, "VmHWM:"), Some(2048 * 1024));
+        assert_eq!(super::status_bytes(status, "VmRSS:"), None);
+    }
+
+    #[test]
+    fn format_bytes() {
+        assert_eq!(super::format_bytes(850), "850B");
+        assert_eq!(super::format_bytes(12_650), "12.4KiB");
+        assert_eq!(super::format_bytes(1_572_864), "1.50MiB");
+        assert_eq!(super::format_bytes(300 << 30), "300GiB");
+    }
+
+    #[test]
+    fn format_table() {
+        let usage = Usage { allocations: 1234, bytes: 2048, peak_heap: 100, peak_rss: None };
+        let rows = vec![Row { day: 12, stage: String::from("star one"), usage }];
+        assert_eq!(
+            super::format_table(&rows),
+            "Day  Stage         Allocs     Bytes  Peak heap  Peak RSS\n 12  star one        1234   2.00KiB       100B         -\n"
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use common::{AocError, Part, Puzzle};
+
+/// Min, median and max duration of a number of runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        assert!(!samples.is_empty(), "Need at least one sample");
+        samples.sort();
+        Stats {
+            runs: samples.len(),
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// One measured stage of a day: parsing or one of the parts.
+pub struct Row {
+    pub day: u32,
+    pub stage: String,
+    pub stats: Stats,
+}
+
+/// Times `f` once and returns its result with the elapsed time.
+pub fn time<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Formats a duration with three significant digits in a readable unit, e.g. "12.3ms".
+pub fn format_duration(d: Duration) -> String {
+    let ns = d.as_nanos() as f64;
+    let (value, unit) = if ns < 1_000.0 {
+        (ns, "ns")
+    } else if ns < 1_000_000.0 {
+        (ns / 1_000.0, "µs")
+    } else if ns < 1_000_000_000.0 {
+        (ns / 1_000_000.0, "ms")
+    } else {
+        (ns / 1_000_000_000.0, "s")
+    };
+
+    if value < 10.0 {
+        format!("{:.2}{}", value, unit)
+    } else if value < 100.0 {
+        format!("{:.1}{}", value, unit)
+    } else {
+        format!("{:.0}{}", value, unit)
+    }
+}
+
+/// Parses `lines` and solves the given parts `runs` times each.
+pub fn bench_day(day: u32, puzzle: &dyn Puzzle, lines: &[String], parts: &[Part], runs: usize) -> Result<Vec<Row>, AocError> {
+    let mut rows: Vec<Row> = Vec::new();
+
+    let mut samples: Vec<Duration> = Vec::with_capacity(runs);
+    let mut input = None;
+    for _ in 0..runs {
+        let (parsed, elapsed) = time(|| puzzle.parse_input(black_box(lines)));
+        samples.push(elapsed);
+        input = Some(parsed?);
+    }
+    let input = input.expect("At least one run");
+    rows.push(Row { day, stage: String::from("parse"), stats: Stats::from_samples(&mut samples) });
+
+    for part in parts {
+        samples.clear();
+        for _ in 0..runs {
+            let (ans, elapsed) = time(|| puzzle.solve(black_box(&*input), *part));
+            black_box(ans);
+            samples.push(elapsed);
+        }
+        rows.push(Row { day, stage: format!("star {}", part), stats: Stats::from_samples(&mut samples) });
+    }
+
+    Ok(rows)
+}
+
+pub fn format_table(rows: &[Row]) -> String {
+    let mut out = format!("{:>3}  {:<8}  {:>4}  {:>8}  {:>8}  {:>8}\n", "Day", "Stage", "Runs", "Min", "Median", "Max");
+    for row in rows {
+        out += &format!(
+            "{:>3}  {:<8}  {:>4}  {:>8}  {:>8}  {:>8}\n",
+            row.day,
+            row.stage,
+            row.stats.runs,
+            format_duration(row.stats.min),
+            format_duration(row.stats.median),
+            format_duration(row.stats.max)
+        );
+    }
+    out
+}
+
+pub fn format_csv(rows: &[Row]) -> String {
+    let mut out = String::from("day,stage,runs,min_ns,median_ns,max_ns\n");
+    for row in rows {
+        out += &format!(
+            "{},{},{},{},{},{}\n",
+            row.day,
+            row.stage,
+            row.stats.runs,
+            row.stats.min.as_nanos(),
+            row.stats.median.as_nanos(),
+            row.stats.max.as_nanos()
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Row, Stats};
+
+    #[test]
+    fn stats() {
+        let mut samples: Vec<Duration> = [5, 1, 9, 3, 7].iter().map(|x| Duration::from_micros(*x)).collect();
+        let stats = Stats::from_samples(&mut samples);
+        assert_eq!(stats.runs, 5);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_micros(5));
+        assert_eq!(stats.max, Duration::from_micros(9));
+    }
+
+    #[test]
+    fn format_duration() {
+        assert_eq!(super::format_duration(Duration::from_nanos(850)), "850ns");
+        assert_eq!(super::format_duration(Duration::from_nanos(12_345)), "12.3µs");
+        assert_eq!(super::format_duration(Duration::from_micros(1_500)), "1.50ms");
+        assert_eq!(super::format_duration(Duration::from_millis(2_000)), "2.00s");
+    }
+
+    #[test]
+    fn format_csv() {
+        let stats = Stats { runs: 3, min: Duration::from_nanos(1), median: Duration::from_nanos(2), max: Duration::from_nanos(3) };
+        let rows = vec![Row { day: 15, stage: String::from("star two"), stats }];
+        assert_eq!(super::format_csv(&rows), "day,stage,runs,min_ns,median_ns,max_ns\n15,star two,3,1,2,3\n");
+    }
+}
@@ -0,0 +1,638 @@
+use common::params;
+use common::{parse_input_arg, InputSource, Part};
+
+use crate::days;
+use crate::output::Format;
+
+pub static USAGE: &str = "Usage: aoc run <days> [--part 1|2] [--input <path> | - | --example] [--time]
+                [--format text|json|csv] [--stream] [--render <out.gif|out.ppm>] [--visualize]
+                [--jobs N] [--param name=value]... [--profile-alloc]
+       aoc bench <days> [--part 1|2] [--runs N] [--csv]
+       aoc verify [<days>] [--record] [--examples]
+       aoc new <day> [--example <file>] [--expect1 <answer>] [--expect2 <answer>]
+       aoc fuzz <days> [--runs N] [--seed S]
+       aoc gen <day> [--size N] [--seed S]
+       aoc repl <day> [--input <path> | --example]
+
+<days> is a day, all, from..to or from..=to, or a comma-separated list of those";
+
+#[derive(Debug, PartialEq)]
+pub struct RunOptions {
+    pub days: Vec<u32>,
+    pub parts: Vec<Part>,
+    /// Input to use instead of `dayNN/input`
+    pub input: Option<InputSource>,
+    /// Report how long parsing and each part took
+    pub time: bool,
+    pub format: Format,
+    /// Solve both parts in a single pass over the input, without reading it into memory
+    pub stream: bool,
+    /// Image file to draw the solution to
+    pub render: Option<String>,
+    /// Play back the simulation in the terminal before solving
+    pub visualize: bool,
+    /// Number of days to solve at the same time when running several; one per CPU if
+    /// not given
+    pub jobs: Option<usize>,
+    /// Puzzle parameters to change from their defaults, in the order given
+    pub params: Vec<(String, String)>,
+    /// Count the allocations of parsing and each part, and report them per day
+    pub profile_alloc: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+    pub days: Vec<u32>,
+    pub parts: Vec<Part>,
+    pub runs: usize,
+    pub csv: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct VerifyOptions {
+    pub days: Vec<u32>,
+    /// Write the current answers for parts that have no known answer yet
+    pub record: bool,
+    /// Check the examples in `examples/` instead of the inputs
+    pub examples: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct NewOptions {
+    pub day: u32,
+    /// File with the example from the puzzle description, copied to `examples/dayNN/example.txt`
+    pub example: Option<String>,
+    pub expect1: Option<String>,
+    pub expect2: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct FuzzOptions {
+    pub days: Vec<u32>,
+    /// Number of mutated inputs per day
+    pub runs: usize,
+    pub seed: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GenOptions {
+    pub day: u32,
+    /// Rough size of the input, in the unit that makes sense for the day
+    pub size: usize,
+    pub seed: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ReplOptions {
+    pub day: u32,
+    /// Input to use instead of `dayNN/input`; not stdin, which has the commands
+    pub input: Option<InputSource>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunOptions),
+    Bench(BenchOptions),
+    Verify(VerifyOptions),
+    New(NewOptions),
+    Fuzz(FuzzOptions),
+    Gen(GenOptions),
+    Repl(ReplOptions),
+}
+
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    match args.first().map(|arg| arg.as_str()) {
+        Some("run") => Ok(Command::Run(parse_run(&args[1..])?)),
+        Some("bench") => Ok(Command::Bench(parse_bench(&args[1..])?)),
+        Some("verify") => Ok(Command::Verify(parse_verify(&args[1..])?)),
+        Some("new") => Ok(Command::New(parse_new(&args[1..])?)),
+        Some("fuzz") => Ok(Command::Fuzz(parse_fuzz(&args[1..])?)),
+        Some("gen") => Ok(Command::Gen(parse_gen(&args[1..])?)),
+        Some("repl") => Ok(Command::Repl(parse_repl(&args[1..])?)),
+        Some(cmd) => Err(format!("unknown command '{}'", cmd)),
+        None => Err(String::from("missing command")),
+    }
+}
+
+fn parse_run(args: &[String]) -> Result<RunOptions, String> {
+    let mut days: Option<Vec<u32>> = None;
+    let mut parts: Vec<Part> = Part::BOTH.to_vec();
+    let mut input: Option<InputSource> = None;
+    let mut time = false;
+    let mut format = Format::Text;
+    let mut stream = false;
+    let mut render: Option<String> = None;
+    let mut visualize = false;
+    let mut jobs: Option<usize> = None;
+    let mut params: Vec<(String, String)> = Vec::new();
+    let mut profile_alloc = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--part" | "-p" => parts = parse_part(iter.next())?,
+            "--time" | "-t" => time = true,
+            "--render" => render = Some(iter.next().ok_or("--render needs a file name")?.clone()),
+            "--visualize" | "-v" => visualize = true,
+            "--format" | "-f" => format = Format::parse(iter.next().ok_or("--format needs a value")?)?,
+            "--stream" => stream = true,
+            "--profile-alloc" => profile_alloc = true,
+            "--jobs" | "-j" => {
+                let value = iter.next().ok_or("--jobs needs a value")?;
+                jobs = match value.parse::<usize>() {
+                    Ok(jobs) if jobs > 0 => Some(jobs),
+                    _ => return Err(format!("invalid number of jobs '{}'", value)),
+                };
+            }
+            "--param" => params.push(params::assignment(iter.next().ok_or("--param needs name=value")?)?),
+            opt if opt.starts_with('-') => {
+                input = Some(parse_input_arg(opt, &mut iter)?.ok_or(format!("unknown option '{}'", opt))?);
+            }
+            spec => {
+                if days.is_some() {
+                    return Err(format!("unexpected argument '{}'", spec));
+                }
+                days = Some(parse_days(spec)?);
+            }
+        }
+    }
+
+    let days = days.ok_or("missing day selection")?;
+    if days.len() > 1 && matches!(input, Some(InputSource::File(_)) | Some(InputSource::Stdin)) {
+        return Err(String::from("an input file or stdin can only be used with a single day"));
+    }
+    if render.is_some() && (days.len() > 1 || stream) {
+        return Err(String::from("--render needs a single day and can not be combined with --stream"));
+    }
+    if visualize && (days.len() > 1 || stream) {
+        return Err(String::from("--visualize needs a single day and can not be combined with --stream"));
+    }
+    if !params.is_empty() && days.len() > 1 {
+        return Err(String::from("--param needs a single day"));
+    }
+    if profile_alloc && (stream || format != Format::Text) {
+        return Err(String::from("--profile-alloc needs the text format and can not be combined with --stream"));
+    }
+
+    Ok(RunOptions { days, parts, input, time, format, stream, render, visualize, jobs, params, profile_alloc })
+}
+
+fn parse_bench(args: &[String]) -> Result<BenchOptions, String> {
+    let mut days: Option<Vec<u32>> = None;
+    let mut parts: Vec<Part> = Part::BOTH.to_vec();
+    let mut runs: usize = 10;
+    let mut csv = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--part" | "-p" => parts = parse_part(iter.next())?,
+            "--runs" | "-n" => {
+                let value = iter.next().ok_or("--runs needs a value")?;
+                runs = match value.parse::<usize>() {
+                    Ok(runs) if runs > 0 => runs,
+                    _ => return Err(format!("invalid number of runs '{}'", value)),
+                };
+            }
+            "--csv" => csv = true,
+            opt if opt.starts_with('-') => return Err(format!("unknown option '{}'", opt)),
+            spec => {
+                if days.is_some() {
+                    return Err(format!("unexpected argument '{}'", spec));
+                }
+                days = Some(parse_days(spec)?);
+            }
+        }
+    }
+
+    Ok(BenchOptions {
+        days: days.ok_or("missing day selection")?,
+        parts,
+        runs,
+        csv,
+    })
+}
+
+fn parse_verify(args: &[String]) -> Result<VerifyOptions, String> {
+    let mut days: Option<Vec<u32>> = None;
+    let mut record = false;
+    let mut examples = false;
+
+    for arg in args {
+        match arg.as_str() {
+            "--record" => record = true,
+            "--examples" => examples = true,
+            opt if opt.starts_with('-') => return Err(format!("unknown option '{}'", opt)),
+            spec => {
+                if days.is_some() {
+                    return Err(format!("unexpected argument '{}'", spec));
+                }
+                days = Some(parse_days(spec)?);
+            }
+        }
+    }
+
+    Ok(VerifyOptions {
+        days: match days {
+            Some(days) => days,
+            None => parse_days("all")?,
+        },
+        record,
+        examples,
+    })
+}
+
+fn parse_new(args: &[String]) -> Result<NewOptions, String> {
+    let mut day: Option<u32> = None;
+    let mut example: Option<String> = None;
+    let mut expect1: Option<String> = None;
+    let mut expect2: Option<String> = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().cloned().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--example" => example = Some(value()?),
+            "--expect1" => expect1 = Some(value()?),
+            "--expect2" => expect2 = Some(value()?),
+            opt if opt.starts_with('-') => return Err(format!("unknown option '{}'", opt)),
+            spec => {
+                if day.is_some() {
+                    return Err(format!("unexpected argument '{}'", spec));
+                }
+                day = match parse_day(spec)? {
+                    d @ 1..=25 => Some(d),
+                    d => return Err(format!("day {} is not an advent day", d)),
+                };
+            }
+        }
+    }
+
+    Ok(NewOptions {
+        day: day.ok_or("missing day")?,
+        example,
+        expect1,
+        expect2,
+    })
+}
+
+fn parse_fuzz(args: &[String]) -> Result<FuzzOptions, String> {
+    let mut days: Option<Vec<u32>> = None;
+    let mut runs: usize = 100_000;
+    let mut seed: u64 = 1;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--runs" | "-n" => {
+                let value = iter.next().ok_or("--runs needs a value")?;
+                runs = value.parse::<usize>().map_err(|_| format!("invalid number of runs '{}'", value))?;
+            }
+            "--seed" => {
+                let value = iter.next().ok_or("--seed needs a value")?;
+                seed = value.parse::<u64>().map_err(|_| format!("invalid seed '{}'", value))?;
+            }
+            opt if opt.starts_with('-') => return Err(format!("unknown option '{}'", opt)),
+            spec => {
+                if days.is_some() {
+                    return Err(format!("unexpected argument '{}'", spec));
+                }
+                days = Some(parse_days(spec)?);
+            }
+        }
+    }
+
+    Ok(FuzzOptions {
+        days: days.ok_or("missing day selection")?,
+        runs,
+        seed,
+    })
+}
+
+fn parse_gen(args: &[String]) -> Result<GenOptions, String> {
+    let mut day: Option<u32> = None;
+    let mut size: usize = 100;
+    let mut seed: u64 = 1;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--size" | "-n" => {
+                let value = iter.next().ok_or("--size needs a value")?;
+                size = value.parse::<usize>().map_err(|_| format!("invalid size '{}'", value))?;
+            }
+            "--seed" => {
+                let value = iter.next().ok_or("--seed needs a value")?;
+                seed = value.parse::<u64>().map_err(|_| format!("invalid seed '{}'", value))?;
+            }
+            opt if opt.starts_with('-') => return Err(format!("unknown option '{}'", opt)),
+            spec => {
+                if day.is_some() {
+                    return Err(format!("unexpected argument '{}'", spec));
+                }
+                day = match parse_days(spec)?.as_slice() {
+                    [day] => Some(*day),
+                    _ => return Err(String::from("inputs can only be generated for a single day")),
+                };
+            }
+        }
+    }
+
+    Ok(GenOptions {
+        day: day.ok_or("missing day")?,
+        size,
+        seed,
+    })
+}
+
+fn parse_repl(args: &[String]) -> Result<ReplOptions, String> {
+    let mut day: Option<u32> = None;
+    let mut input: Option<InputSource> = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            opt if opt.starts_with('-') => {
+                input = Some(parse_input_arg(opt, &mut iter)?.ok_or(format!("unknown option '{}'", opt))?);
+            }
+            spec => {
+                if day.is_some() {
+                    return Err(format!("unexpected argument '{}'", spec));
+                }
+                day = match parse_days(spec)?.as_slice() {
+                    [day] => Some(*day),
+                    _ => return Err(String::from("the repl explores a single day")),
+                };
+            }
+        }
+    }
+
+    if input == Some(InputSource::Stdin) {
+        return Err(String::from("the repl reads its commands from stdin, use --input <path>"));
+    }
+
+    Ok(ReplOptions { day: day.ok_or("missing day")?, input })
+}
+
+fn parse_part(value: Option<&String>) -> Result<Vec<Part>, String> {
+    let part = value.ok_or("--part needs a value")?;
+    match part.as_str() {
+        "1" => Ok(vec![Part::One]),
+        "2" => Ok(vec![Part::Two]),
+        _ => Err(format!("invalid part '{}', expected 1 or 2", part)),
+    }
+}
+
+fn parse_day(day: &str) -> Result<u32, String> {
+    day.parse::<u32>()
+        .map_err(|_| format!("invalid day '{}'", day))
+}
+
+/// Parses a day selection: "all", a single day ("14"), a range ("5..9" or "5..=9")
+/// or a comma-separated list of those.
+pub fn parse_days(spec: &str) -> Result<Vec<u32>, String> {
+    let mut result: Vec<u32> = Vec::new();
+
+    for part in spec.split(',') {
+        if part == "all" {
+            result.extend(days::DAYS.iter().map(|d| d.day));
+        } else if let Some((from, to)) = part.split_once("..=") {
+            result.extend(parse_day(from)?..=parse_day(to)?);
+        } else if let Some((from, to)) = part.split_once("..") {
+            result.extend(parse_day(from)?..parse_day(to)?);
+        } else {
+            result.push(parse_day(part)?);
+        }
+    }
+
+    if result.is_empty() {
+        return Err(format!("day selection '{}' is empty", spec));
+    }
+
+    if let Some(day) = result.iter().find(|day| days::find(**day).is_none()) {
+        return Err(format!("day {} is not implemented", day));
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{InputSource, Part};
+
+    use crate::output::Format;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_days() {
+        assert_eq!(super::parse_days("14"), Ok(vec![14]));
+        assert_eq!(super::parse_days("5..9"), Ok(vec![5, 6, 7, 8]));
+        assert_eq!(super::parse_days("5..=9"), Ok(vec![5, 6, 7, 8, 9]));
+        assert_eq!(super::parse_days("1,3..=4"), Ok(vec![1, 3, 4]));
+        assert_eq!(super::parse_days("all").unwrap().len(), super::days::DAYS.len());
+        assert!(super::parse_days("9..5").is_err());
+        assert!(super::parse_days("0").is_err());
+        assert!(super::parse_days("x").is_err());
+    }
+
+    #[test]
+    fn parse_run() {
+        assert_eq!(
+            super::parse_args(&args("run 14 --part 2")),
+            Ok(super::Command::Run(super::RunOptions { days: vec![14], parts: vec![Part::Two], input: None, time: false, format: Format::Text, stream: false, render: None, visualize: false, jobs: None, params: vec![], profile_alloc: false }))
+        );
+        assert_eq!(
+            super::parse_args(&args("run 1..=2 --time --format json")),
+            Ok(super::Command::Run(super::RunOptions {
+                days: vec![1, 2],
+                parts: Part::BOTH.to_vec(),
+                input: None,
+                time: true,
+                format: Format::Json,
+                stream: false,
+                render: None,
+                visualize: false,
+                jobs: None,
+                params: vec![],
+                profile_alloc: false
+            }))
+        );
+        assert_eq!(
+            super::parse_args(&args("run 5 --stream -")),
+            Ok(super::Command::Run(super::RunOptions {
+                days: vec![5],
+                parts: Part::BOTH.to_vec(),
+                input: Some(InputSource::Stdin),
+                time: false,
+                format: Format::Text,
+                stream: true,
+                render: None,
+                visualize: false,
+                jobs: None,
+                params: vec![],
+                profile_alloc: false
+            }))
+        );
+        assert_eq!(
+            super::parse_args(&args("run 11 --example --render flashes.gif")),
+            Ok(super::Command::Run(super::RunOptions {
+                days: vec![11],
+                parts: Part::BOTH.to_vec(),
+                input: Some(InputSource::Example),
+                time: false,
+                format: Format::Text,
+                stream: false,
+                render: Some(String::from("flashes.gif")),
+                visualize: false,
+                jobs: None,
+                params: vec![],
+                profile_alloc: false
+            }))
+        );
+        assert!(super::parse_args(&args("run 9..=11 --render out.gif")).is_err());
+        assert!(super::parse_args(&args("run 5 --stream --render out.gif")).is_err());
+        assert!(super::parse_args(&args("run 5 --render")).is_err());
+        match super::parse_args(&args("run 17 --visualize --example")) {
+            Ok(super::Command::Run(opts)) => assert!(opts.visualize),
+            other => panic!("Unexpected {:?}", other),
+        }
+        assert!(super::parse_args(&args("run all --visualize")).is_err());
+        match super::parse_args(&args("run all -j 4")) {
+            Ok(super::Command::Run(opts)) => assert_eq!(opts.jobs, Some(4)),
+            other => panic!("Unexpected {:?}", other),
+        }
+        assert!(super::parse_args(&args("run all --jobs 0")).is_err());
+        match super::parse_args(&args("run 6 --param days_two=1000 --param days_one=18")) {
+            Ok(super::Command::Run(opts)) => assert_eq!(
+                opts.params,
+                vec![(String::from("days_two"), String::from("1000")), (String::from("days_one"), String::from("18"))]
+            ),
+            other => panic!("Unexpected {:?}", other),
+        }
+        assert!(super::parse_args(&args("run 6 --param days")).is_err());
+        assert!(super::parse_args(&args("run 6..=7 --param days_two=1000")).is_err());
+        match super::parse_args(&args("run 12,16,18 --profile-alloc")) {
+            Ok(super::Command::Run(opts)) => assert!(opts.profile_alloc),
+            other => panic!("Unexpected {:?}", other),
+        }
+        assert!(super::parse_args(&args("run 5 --profile-alloc --stream")).is_err());
+        assert!(super::parse_args(&args("run 5 --profile-alloc --format csv")).is_err());
+        assert!(super::parse_args(&args("run 1 --format xml")).is_err());
+        assert!(super::parse_args(&args("run --part 3 1")).is_err());
+        assert!(super::parse_args(&args("run")).is_err());
+        assert!(super::parse_args(&args("walk 1")).is_err());
+    }
+
+    #[test]
+    fn parse_run_input() {
+        let input = |line: &str| match super::parse_args(&args(line)) {
+            Ok(super::Command::Run(opts)) => Ok(opts.input),
+            Ok(cmd) => panic!("Unexpected command {:?}", cmd),
+            Err(e) => Err(e),
+        };
+        assert_eq!(input("run 5 --input big.txt"), Ok(Some(InputSource::File(String::from("big.txt")))));
+        assert_eq!(input("run 5 -"), Ok(Some(InputSource::Stdin)));
+        assert_eq!(input("run all --example"), Ok(Some(InputSource::Example)));
+        assert!(input("run all -").is_err());
+        assert!(input("run 5 --input").is_err());
+    }
+
+    #[test]
+    fn parse_bench() {
+        assert_eq!(
+            super::parse_args(&args("bench 15,17 --runs 5 --csv")),
+            Ok(super::Command::Bench(super::BenchOptions { days: vec![15, 17], parts: Part::BOTH.to_vec(), runs: 5, csv: true }))
+        );
+        assert_eq!(
+            super::parse_args(&args("bench all -p 1")),
+            Ok(super::Command::Bench(super::BenchOptions {
+                days: super::parse_days("all").unwrap(),
+                parts: vec![Part::One],
+                runs: 10,
+                csv: false
+            }))
+        );
+        assert!(super::parse_args(&args("bench 1 --runs 0")).is_err());
+        assert!(super::parse_args(&args("bench 1 --example")).is_err());
+    }
+
+    #[test]
+    fn parse_verify() {
+        assert_eq!(
+            super::parse_args(&args("verify")),
+            Ok(super::Command::Verify(super::VerifyOptions { days: super::parse_days("all").unwrap(), record: false, examples: false }))
+        );
+        assert_eq!(
+            super::parse_args(&args("verify 14 --record")),
+            Ok(super::Command::Verify(super::VerifyOptions { days: vec![14], record: true, examples: false }))
+        );
+        assert_eq!(
+            super::parse_args(&args("verify 16..=18 --examples")),
+            Ok(super::Command::Verify(super::VerifyOptions { days: vec![16, 17, 18], record: false, examples: true }))
+        );
+        assert!(super::parse_args(&args("verify --part 1")).is_err());
+    }
+
+    #[test]
+    fn parse_new() {
+        assert_eq!(
+            super::parse_args(&args("new 19 --example ex.txt --expect1 79")),
+            Ok(super::Command::New(super::NewOptions {
+                day: 19,
+                example: Some(String::from("ex.txt")),
+                expect1: Some(String::from("79")),
+                expect2: None
+            }))
+        );
+        assert!(super::parse_args(&args("new 26")).is_err());
+        assert!(super::parse_args(&args("new 19 --expect2")).is_err());
+        assert!(super::parse_args(&args("new")).is_err());
+    }
+
+    #[test]
+    fn parse_fuzz() {
+        assert_eq!(
+            super::parse_args(&args("fuzz 16,18 --runs 500 --seed 7")),
+            Ok(super::Command::Fuzz(super::FuzzOptions { days: vec![16, 18], runs: 500, seed: 7 }))
+        );
+        assert_eq!(
+            super::parse_args(&args("fuzz all")),
+            Ok(super::Command::Fuzz(super::FuzzOptions { days: super::parse_days("all").unwrap(), runs: 100_000, seed: 1 }))
+        );
+        assert!(super::parse_args(&args("fuzz 1 --seed x")).is_err());
+        assert!(super::parse_args(&args("fuzz")).is_err());
+    }
+
+    #[test]
+    fn parse_gen() {
+        assert_eq!(
+            super::parse_args(&args("gen 15 --size 500 --seed 3")),
+            Ok(super::Command::Gen(super::GenOptions { day: 15, size: 500, seed: 3 }))
+        );
+        assert_eq!(
+            super::parse_args(&args("gen 4")),
+            Ok(super::Command::Gen(super::GenOptions { day: 4, size: 100, seed: 1 }))
+        );
+        assert!(super::parse_args(&args("gen all")).is_err());
+        assert!(super::parse_args(&args("gen 4 --size -1")).is_err());
+        assert!(super::parse_args(&args("gen")).is_err());
+    }
+
+    #[test]
+    fn parse_repl() {
+        assert_eq!(
+            super::parse_args(&args("repl 13 --example")),
+            Ok(super::Command::Repl(super::ReplOptions { day: 13, input: Some(InputSource::Example) }))
+        );
+        assert_eq!(
+            super::parse_args(&args("repl 11")),
+            Ok(super::Command::Repl(super::ReplOptions { day: 11, input: None }))
+        );
+        assert!(super::parse_args(&args("repl 11 -")).is_err());
+        assert!(super::parse_args(&args("repl 11..13")).is_err());
+        assert!(super::parse_args(&args("repl")).is_err());
+    }
+}
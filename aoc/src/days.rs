@@ -0,0 +1,31 @@
+use common::Puzzle;
+
+pub struct Day {
+    pub day: u32,
+    pub solution: fn() -> Box<dyn Puzzle>,
+}
+
+pub static DAYS: &[Day] = &[
+    Day { day: 1, solution: || Box::new(day01::Day01) },
+    Day { day: 2, solution: || Box::new(day02::Day02) },
+    Day { day: 3, solution: || Box::new(day03::Day03::default()) },
+    Day { day: 4, solution: || Box::new(day04::Day04) },
+    Day { day: 5, solution: || Box::new(day05::Day05) },
+    Day { day: 6, solution: || Box::new(day06::Day06::default()) },
+    Day { day: 7, solution: || Box::new(day07::Day07) },
+    Day { day: 8, solution: || Box::new(day08::Day08) },
+    Day { day: 9, solution: || Box::new(day09::Day09) },
+    Day { day: 10, solution: || Box::new(day10::Day10) },
+    Day { day: 11, solution: || Box::new(day11::Day11::default()) },
+    Day { day: 12, solution: || Box::new(day12::Day12) },
+    Day { day: 13, solution: || Box::new(day13::Day13) },
+    Day { day: 14, solution: || Box::new(day14::Day14::default()) },
+    Day { day: 15, solution: || Box::new(day15::Day15::default()) },
+    Day { day: 16, solution: || Box::new(day16::Day16) },
+    Day { day: 17, solution: || Box::new(day17::Day17::default()) },
+    Day { day: 18, solution: || Box::new(day18::Day18) },
+];
+
+pub fn find(day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
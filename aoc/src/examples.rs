@@ -0,0 +1,126 @@
+//! Examples with known answers: every `examples/dayNN/<name>.txt` is an input of day NN,
+//! and `<name>.toml` next to it holds the expected answers in the format of
+//! `answers.toml`, leaving out the parts that are not known. Adding an edge case is
+//! dropping in such a pair of files; `aoc verify --examples` and the tests of the
+//! runner find and check all of them.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use common::{Answer, Answers, Part};
+
+use crate::days;
+
+/// Directory of the examples, relative to the workspace root like the inputs
+pub const EXAMPLES_DIR: &str = "examples";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub day: u32,
+    /// File name without the extension
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl Example {
+    /// The file with the expected answers.
+    pub fn answers_path(&self) -> PathBuf {
+        self.path.with_extension("toml")
+    }
+
+    /// Parses the example with the puzzle of its day and solves `parts`.
+    pub fn solve(&self, parts: &[Part]) -> Result<Vec<(Part, Answer)>, String> {
+        let solver = days::find(self.day).ok_or(format!("day {} is not implemented", self.day))?;
+        let puzzle = (solver.solution)();
+        let text = fs::read_to_string(&self.path)
+            .map_err(|e| format!("unreadable example {}: {}", self.path.display(), e))?;
+        let lines: Vec<String> = text.lines().map(|line| line.to_string()).collect();
+        let input = puzzle
+            .parse_input(&lines)
+            .map_err(|e| format!("{}:{}", self.path.display(), e))?;
+        Ok(parts.iter().map(|part| (*part, puzzle.solve(&*input, *part))).collect())
+    }
+}
+
+/// The examples of `days` in `dir`, by day and then by name. A day without a directory
+/// has no examples.
+pub fn discover(dir: &Path, days: &[u32]) -> Result<Vec<Example>, String> {
+    let mut examples = Vec::new();
+    for &day in days {
+        let day_dir = dir.join(format!("day{:02}", day));
+        let unreadable = |e: io::Error| format!("unreadable directory {}: {}", day_dir.display(), e);
+        let entries = match fs::read_dir(&day_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(unreadable(e)),
+        };
+        let mut paths = entries.map(|entry| entry.map(|e| e.path())).collect::<Result<Vec<_>, _>>().map_err(unreadable)?;
+        paths.retain(|path| path.extension().is_some_and(|ext| ext == "txt"));
+        paths.sort();
+        for path in paths {
+            let name = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+            examples.push(Example { day, name, path });
+        }
+    }
+    Ok(examples)
+}
+
+/// The expected answers of `example`; an example without answers file has none.
+pub fn load_answers(example: &Example) -> Result<Answers, String> {
+    Answers::load(&example.answers_path().to_string_lossy())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use common::Part;
+
+    use crate::days;
+
+    fn examples_dir() -> &'static Path {
+        Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../examples"))
+    }
+
+    #[test]
+    fn discover() {
+        let examples = super::discover(examples_dir(), &[12, 99]).unwrap();
+        let names: Vec<&str> = examples.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["example", "large", "small"]);
+        assert!(examples.iter().all(|e| e.day == 12));
+        assert!(examples[0].answers_path().ends_with("examples/day12/example.toml"));
+    }
+
+    /// Every example gives the answers in its answers file.
+    #[test]
+    fn examples() {
+        let all_days: Vec<u32> = days::DAYS.iter().map(|d| d.day).collect();
+        let examples = super::discover(examples_dir(), &all_days).unwrap();
+        assert!(examples.len() >= all_days.len(), "Every day has an example");
+
+        let mut failures = Vec::new();
+        for example in &examples {
+            let label = format!("day {} {}", example.day, example.name);
+            if !fs::metadata(example.answers_path()).is_ok_and(|meta| meta.is_file()) {
+                failures.push(format!("{}: no answers file {}", label, example.answers_path().display()));
+                continue;
+            }
+            let answers = super::load_answers(example).unwrap();
+            let parts: Vec<Part> = Part::BOTH.into_iter().filter(|part| answers.get(*part).is_some()).collect();
+            match example.solve(&parts) {
+                Ok(solved) => {
+                    for (part, ans) in solved {
+                        let expected = answers.get(part).unwrap();
+                        if !ans.is_solved() || expected.to_string() != ans.to_string() {
+                            failures.push(format!("{} star {}: expected {}, got {}", label, part, expected, ans));
+                        }
+                    }
+                }
+                Err(e) => failures.push(format!("{}: {}", label, e)),
+            }
+        }
+        assert!(failures.is_empty(), "{} of {} examples failed:\n{}", failures.len(), examples.len(), failures.join("\n"));
+    }
+}
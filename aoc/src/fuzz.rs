@@ -0,0 +1,269 @@
+//! A small mutation fuzzer for the days that runs on stable Rust, next to the
+//! cargo-fuzz targets in `fuzz/`. It parses every input and solves both parts of the
+//! ones that parse, and streams them through the days that can, checking that both
+//! ways give the same answers. The corpus is seeded from the examples in `examples/dayNN`, plus
+//! whatever cargo-fuzz saved in `fuzz/corpus/dayNN`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+
+use common::input::lossy_lines;
+use common::rng::Rng;
+use common::{LineReader, Part, Puzzle};
+
+use crate::examples::{self, EXAMPLES_DIR};
+use crate::panics;
+
+/// Bytes that mean something to at least one of the parsers.
+const INTERESTING: &[u8] = b"0123456789,-> []{}()<>=.|#;:xyzABCDEF\n\n";
+
+/// Replacements for numbers, to reach overflows and out of range values: the largest
+/// values of the integer types still parse, so they get past the parsers.
+const NUMBERS: &[&str] = &[
+    "0",
+    "-1",
+    "9",
+    "10",
+    "255",
+    "65536",
+    "2147483647",
+    "-2147483648",
+    "4294967295",
+    "4294967296",
+    "9223372036854775807",
+    "-9223372036854775808",
+    "18446744073709551615",
+    "99999999999999999999999",
+];
+
+pub fn corpus_dir(day: u32) -> String {
+    format!("fuzz/corpus/day{:02}", day)
+}
+
+pub fn artifacts_dir(day: u32) -> String {
+    format!("fuzz/artifacts/day{:02}", day)
+}
+
+/// The examples of the day plus every file in its corpus directory, if it exists. The
+/// embedded `example` only stands in when neither has any input.
+pub fn load_corpus(day: u32, example: &str) -> Result<Vec<Vec<u8>>, String> {
+    let mut corpus = Vec::new();
+    for example in examples::discover(Path::new(EXAMPLES_DIR), &[day])? {
+        let path = &example.path;
+        corpus.push(fs::read(path).map_err(|e| format!("unreadable example {}: {}", path.display(), e))?);
+    }
+    let dir = corpus_dir(day);
+    if Path::new(&dir).is_dir() {
+        let entries = fs::read_dir(&dir).map_err(|e| format!("unreadable corpus {}: {}", dir, e))?;
+        let mut paths: Vec<_> = entries.filter_map(|entry| entry.ok().map(|e| e.path())).collect();
+        paths.sort();
+        for path in paths {
+            corpus.push(fs::read(&path).map_err(|e| format!("unreadable corpus file {}: {}", path.display(), e))?);
+        }
+    }
+    if corpus.is_empty() {
+        corpus.push(example.as_bytes().to_vec());
+    }
+    Ok(corpus)
+}
+
+/// Applies one to four random edits to `input`.
+pub fn mutate(rng: &mut Rng, input: &[u8], corpus: &[Vec<u8>]) -> Vec<u8> {
+    let mut data = input.to_vec();
+    for _ in 0..rng.range(1..5) {
+        let pos = rng.range(0..data.len() + 1);
+        match rng.range(0..7) {
+            // Overwrite a byte
+            0 if pos < data.len() => {
+                data[pos] = if rng.chance(3, 4) { *rng.pick(INTERESTING) } else { rng.range(0..256) as u8 };
+            }
+            // Insert a byte
+            1 => data.insert(pos, *rng.pick(INTERESTING)),
+            // Delete a range
+            2 => {
+                let end = rng.range(pos..data.len() + 1);
+                data.drain(pos..end);
+            }
+            // Duplicate a range
+            3 => {
+                let end = rng.range(pos..data.len() + 1).min(pos + 64);
+                let copy = data[pos..end].to_vec();
+                let at = rng.range(0..data.len() + 1);
+                data.splice(at..at, copy);
+            }
+            // Cut off the rest
+            4 => data.truncate(pos),
+            // Splice in part of another corpus entry
+            5 => {
+                let other = rng.pick(corpus);
+                let from = rng.range(0..other.len() + 1);
+                let to = rng.range(from..other.len() + 1);
+                data.splice(pos..pos, other[from..to].iter().copied());
+            }
+            // Replace the number at or after `pos`
+            _ => {
+                if let Some(start) = data[pos.min(data.len())..].iter().position(|b| b.is_ascii_digit()) {
+                    let start = pos + start;
+                    let len = data[start..].iter().take_while(|b| b.is_ascii_digit()).count();
+                    data.splice(start..start + len, rng.pick(NUMBERS).bytes());
+                }
+            }
+        }
+    }
+    data
+}
+
+/// Parses `data` and solves both parts if it parses, then streams it if the day can.
+/// Returns the panic message if any of them panics, or if the streamed answers differ
+/// from the parsed ones.
+fn try_solve(puzzle: &dyn Puzzle, data: &[u8]) -> Option<String> {
+    let lines = lossy_lines(data);
+    panics::catch(|| {
+        let answers = puzzle
+            .parse_input(&lines)
+            .ok()
+            .map(|input| Part::BOTH.map(|part| puzzle.solve(&*input, part)));
+        // The same lines the parser saw, so invalid UTF-8 does not end the stream early
+        let mut reader = LineReader::new(Cursor::new(lines.join("\n")));
+        if let (Some([one, two]), Some(Ok(streamed))) = (answers, puzzle.stream(&mut reader)) {
+            assert_eq!((one, two), streamed, "streamed answers differ from parsed answers");
+        }
+    })
+    .err()
+}
+
+/// A panic found by the fuzzer: the smallest input seen for it and how often it occurred.
+#[derive(Debug)]
+pub struct Crash {
+    pub message: String,
+    pub input: Vec<u8>,
+    pub count: usize,
+}
+
+/// Feeds `runs` mutated inputs to `puzzle` and returns the distinct panics, keyed by
+/// their message and location.
+pub fn fuzz_day(puzzle: &dyn Puzzle, corpus: &[Vec<u8>], runs: usize, rng: &mut Rng) -> Vec<Crash> {
+    panics::quietly(|| fuzz_quietly(puzzle, corpus, runs, rng))
+}
+
+fn fuzz_quietly(puzzle: &dyn Puzzle, corpus: &[Vec<u8>], runs: usize, rng: &mut Rng) -> Vec<Crash> {
+    let mut crashes: BTreeMap<String, Crash> = BTreeMap::new();
+    let mut record = |data: Vec<u8>, message: String| {
+        let crash = crashes.entry(message.clone()).or_insert(Crash { message, input: data.clone(), count: 0 });
+        crash.count += 1;
+        if data.len() < crash.input.len() {
+            crash.input = data;
+        }
+    };
+
+    for data in corpus {
+        if let Some(message) = try_solve(puzzle, data) {
+            record(data.clone(), message);
+        }
+    }
+    for _ in 0..runs {
+        let idx = rng.range(0..corpus.len());
+        let data = mutate(rng, &corpus[idx], corpus);
+        if let Some(message) = try_solve(puzzle, &data) {
+            record(data, message);
+        }
+    }
+
+    crashes.into_values().collect()
+}
+
+/// Writes the input of each crash to the artifacts directory of the day.
+pub fn save_crashes(day: u32, crashes: &[Crash]) -> Result<Vec<String>, String> {
+    let dir = artifacts_dir(day);
+    if crashes.is_empty() {
+        return Ok(Vec::new());
+    }
+    fs::create_dir_all(&dir).map_err(|e| format!("could not create {}: {}", dir, e))?;
+    let mut paths = Vec::new();
+    for (idx, crash) in crashes.iter().enumerate() {
+        let path = format!("{}/crash-{}", dir, idx + 1);
+        fs::write(&path, &crash.input).map_err(|e| format!("could not write {}: {}", path, e))?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use common::rng::Rng;
+
+    #[test]
+    fn mutate() {
+        let corpus = vec![b"1,2,3\n4,5,6".to_vec()];
+        let mut rng = Rng::new(1);
+        let mutated: Vec<Vec<u8>> = (0..20).map(|_| super::mutate(&mut rng, &corpus[0], &corpus)).collect();
+        assert!(mutated.iter().any(|m| *m != corpus[0]));
+
+        let mut again = Rng::new(1);
+        assert_eq!(super::mutate(&mut again, &corpus[0], &corpus), mutated[0]);
+    }
+
+    #[test]
+    fn finds_panics() {
+        struct Picky;
+        impl common::Solution for Picky {
+            type Input = usize;
+            fn example(&self) -> &'static str {
+                "12"
+            }
+            fn parse(&self, lines: &[String]) -> Result<usize, common::AocError> {
+                // Panics on an empty input or a first line that is not a number
+                Ok(lines[0].parse::<usize>().unwrap())
+            }
+            fn part_one(&self, _: &usize) -> common::Answer {
+                0usize.into()
+            }
+            fn part_two(&self, num: &usize) -> common::Answer {
+                // Panics on numbers from 100 on, which parse fine
+                [0; 100][*num].into()
+            }
+        }
+
+        let corpus = vec![b"12".to_vec()];
+        let crashes = super::fuzz_day(&Picky, &corpus, 200, &mut Rng::new(3));
+        assert!(crashes.iter().any(|c| c.message.contains("ParseIntError")));
+        assert!(crashes.iter().any(|c| c.message.contains("index out of bounds")));
+        assert!(crashes.iter().all(|c| c.message.contains("aoc/src/fuzz.rs")));
+    }
+
+    #[test]
+    fn checks_streams() {
+        struct Sloppy;
+        impl common::Solution for Sloppy {
+            type Input = Vec<String>;
+            fn example(&self) -> &'static str {
+                "1"
+            }
+            fn parse(&self, lines: &[String]) -> Result<Vec<String>, common::AocError> {
+                Ok(lines.to_vec())
+            }
+            fn part_one(&self, lines: &Vec<String>) -> common::Answer {
+                lines.len().into()
+            }
+            fn part_two(&self, _: &Vec<String>) -> common::Answer {
+                0usize.into()
+            }
+            fn solve_stream(
+                &self,
+                lines: &mut common::LineReader,
+            ) -> Option<Result<(common::Answer, common::Answer), common::AocError>> {
+                // Forgets the last line
+                let mut count = 0usize;
+                while lines.next_line().is_some() {
+                    count += 1;
+                }
+                Some(Ok((count.saturating_sub(1).into(), 0usize.into())))
+            }
+        }
+
+        let crashes = super::fuzz_day(&Sloppy, &[b"1".to_vec()], 10, &mut Rng::new(5));
+        assert!(crashes.iter().any(|c| c.message.contains("streamed answers differ")));
+    }
+}
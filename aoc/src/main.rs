@@ -0,0 +1,467 @@
+mod alloc;
+mod bench;
+mod cli;
+mod days;
+mod examples;
+mod fuzz;
+mod output;
+mod panics;
+mod pool;
+mod repl;
+mod scaffold;
+mod visualize;
+
+use std::env;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+use std::vec::Vec;
+
+use cli::{BenchOptions, Command, FuzzOptions, GenOptions, ReplOptions, RunOptions, VerifyOptions};
+use common::rng::Rng;
+use common::{Answer, Answers, InputSource, Part, Puzzle};
+use output::{Format, Outcome, StageResult};
+
+#[global_allocator]
+static ALLOCATOR: alloc::CountingAllocator = alloc::CountingAllocator;
+
+fn input_path(day: u32) -> String {
+    format!("day{:02}/input", day)
+}
+
+fn answers_path(day: u32) -> String {
+    format!("day{:02}/answers.toml", day)
+}
+
+fn print_answer(part: Part, ans: &Answer, elapsed: Option<Duration>) {
+    let time = elapsed.map(|d| format!(" ({})", bench::format_duration(d)));
+    if ans.is_multiline() {
+        println!("Star {}{}:\n{}", part, time.unwrap_or_default(), ans);
+    } else {
+        println!("Star {}: {}{}", part, ans, time.unwrap_or_default());
+    }
+}
+
+/// Prints the answer to `part` of `day` in the format of `opts`. A part that could not
+/// be solved is reported on stderr instead; returns whether it was solved.
+fn report(opts: &RunOptions, day: u32, part: Part, ans: &Answer, elapsed: Duration) -> bool {
+    if !ans.is_solved() {
+        eprintln!("Day {} star {}: NOT SOLVED: {}", day, part, ans);
+        return false;
+    }
+    match opts.format {
+        Format::Text => print_answer(part, ans, if opts.time { Some(elapsed) } else { None }),
+        format => println!("{}", output::record(format, day, part, ans, elapsed)),
+    }
+    true
+}
+
+/// Changes the parameters of `puzzle`, listing the ones it has when a name is unknown.
+fn set_params(puzzle: &mut dyn Puzzle, day: u32, params: &[(String, String)]) -> Result<(), String> {
+    for (name, value) in params {
+        if !puzzle.parameters().iter().any(|param| param.name == name) {
+            return match puzzle.parameters() {
+                [] => Err(format!("day {} has no parameters", day)),
+                known => Err(format!("unknown parameter '{}', day {} has:\n{}", name, day, common::params::describe(known).trim_end())),
+            };
+        }
+        puzzle.set_parameter(name, value)?;
+    }
+    Ok(())
+}
+
+/// Runs `f`, adding its allocations to `profile` as `stage` when profiling.
+fn profiled<T, F: FnOnce() -> T>(profile: &mut Option<Vec<alloc::Row>>, day: u32, stage: String, f: F) -> T {
+    match profile {
+        Some(rows) => {
+            let (result, usage) = alloc::measure(f);
+            rows.push(alloc::Row { day, stage, usage });
+            result
+        }
+        None => f(),
+    }
+}
+
+fn run(opts: &RunOptions) -> Result<(), String> {
+    // Allocations are counted for the whole process, so profiled days run one by one
+    if opts.days.len() > 1 && !opts.stream && !opts.profile_alloc {
+        return run_parallel(opts);
+    }
+
+    let mut profile = if opts.profile_alloc { Some(Vec::new()) } else { None };
+    let mut unsolved = 0;
+
+    if let Some(header) = output::header(opts.format) {
+        println!("{}", header);
+    }
+
+    for day in &opts.days {
+        let solver = days::find(*day).ok_or(format!("day {} is not implemented", day))?;
+        let mut puzzle = (solver.solution)();
+        set_params(&mut *puzzle, *day, &opts.params)?;
+        let source = match &opts.input {
+            Some(source) => source.clone(),
+            None => InputSource::File(input_path(*day)),
+        };
+        if opts.stream {
+            unsolved += run_stream(opts, *day, &*puzzle, &source)?;
+            continue;
+        }
+
+        let lines = source.read_lines(puzzle.example_data())?;
+        let (input, elapsed) =
+            profiled(&mut profile, *day, String::from("parse"), || bench::time(|| puzzle.parse_input(&lines)));
+        let input = input.map_err(|e| format!("{}:{}", source.name(), e))?;
+        if opts.visualize {
+            let frames = puzzle.visualize_input(&*input).ok_or(format!("day {} has nothing to visualize", day))?;
+            visualize::play(&frames)?;
+        }
+
+        if opts.format == Format::Text {
+            println!("Day {}", day);
+            if opts.time {
+                println!("Parsed in {}", bench::format_duration(elapsed));
+            }
+        }
+        for part in &opts.parts {
+            let (ans, elapsed) =
+                profiled(&mut profile, *day, format!("star {}", part), || bench::time(|| puzzle.solve(&*input, *part)));
+            if !report(opts, *day, *part, &ans, elapsed) {
+                unsolved += 1;
+            }
+        }
+
+        if let Some(path) = &opts.render {
+            let anim = puzzle.render_input(&*input).ok_or(format!("day {} has nothing to render", day))?;
+            let paths = anim.save(path)?;
+            if opts.format == Format::Text {
+                match paths.as_slice() {
+                    [path] => println!("Rendered {} frames to {}", anim.frames().len(), path),
+                    [first, .., last] => println!("Rendered {} frames to {} .. {}", paths.len(), first, last),
+                    [] => (),
+                }
+            }
+        }
+    }
+
+    if let Some(rows) = profile {
+        print!("\n{}", alloc::format_table(&rows));
+    }
+
+    if unsolved > 0 {
+        return Err(format!("{} part{} could not be solved", unsolved, if unsolved == 1 { "" } else { "s" }));
+    }
+    Ok(())
+}
+
+/// Solves several days at the same time on a pool of threads. A day that fails or
+/// panics is reported in the results without stopping the others.
+fn run_parallel(opts: &RunOptions) -> Result<(), String> {
+    let threads = opts.jobs.unwrap_or_else(pool::default_threads).min(opts.days.len());
+    let (results, wall_time) =
+        bench::time(|| panics::quietly(|| pool::map(&opts.days, threads, |day| solve_day(opts, *day))));
+    let results: Vec<StageResult> = results.into_iter().flatten().collect();
+
+    match opts.format {
+        Format::Text => print!("{}", output::summary(&results, wall_time, threads)),
+        format => {
+            if let Some(header) = output::header(format) {
+                println!("{}", header);
+            }
+            for result in &results {
+                match (&result.outcome, result.part) {
+                    (Outcome::Solved(ans), Some(part)) => {
+                        println!("{}", output::record(format, result.day, part, ans, result.elapsed))
+                    }
+                    (Outcome::Failed(e), _) => eprintln!("Day {} {}: FAILED: {}", result.day, result.stage(), e),
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    let failed = opts
+        .days
+        .iter()
+        .filter(|day| results.iter().any(|r| r.day == **day && matches!(r.outcome, Outcome::Failed(_))))
+        .count();
+    if failed > 0 {
+        return Err(format!("{} of {} days failed", failed, opts.days.len()));
+    }
+    Ok(())
+}
+
+/// Parses the input of `day` and solves the selected parts, turning errors and panics
+/// into failed stages.
+fn solve_day(opts: &RunOptions, day: u32) -> Vec<StageResult> {
+    let failed = |e: String, elapsed: Duration| vec![StageResult { day, part: None, outcome: Outcome::Failed(e), elapsed }];
+    let solver = match days::find(day) {
+        Some(solver) => solver,
+        None => return failed(format!("day {} is not implemented", day), Duration::ZERO),
+    };
+    let puzzle = (solver.solution)();
+    let source = match &opts.input {
+        Some(source) => source.clone(),
+        None => InputSource::File(input_path(day)),
+    };
+    let lines = match source.read_lines(puzzle.example_data()) {
+        Ok(lines) => lines,
+        Err(e) => return failed(e, Duration::ZERO),
+    };
+    let (input, elapsed) = bench::time(|| panics::catch(|| puzzle.parse_input(&lines)));
+    let input = match input {
+        Ok(Ok(input)) => input,
+        Ok(Err(e)) => return failed(format!("{}:{}", source.name(), e), elapsed),
+        Err(e) => return failed(e, elapsed),
+    };
+
+    let mut results = vec![StageResult { day, part: None, outcome: Outcome::Parsed, elapsed }];
+    for part in &opts.parts {
+        let (ans, elapsed) = bench::time(|| panics::catch(|| puzzle.solve(&*input, *part)));
+        let outcome = match ans {
+            Ok(Answer::Unsolved(why)) => Outcome::Failed(why),
+            Ok(ans) => Outcome::Solved(ans),
+            Err(e) => Outcome::Failed(e),
+        };
+        results.push(StageResult { day, part: Some(*part), outcome, elapsed });
+    }
+    results
+}
+
+/// Solves a day in one pass over its input; both parts are solved together, so the
+/// time reported for each part is the time of the whole pass. Returns the number of
+/// parts that could not be solved.
+fn run_stream(opts: &RunOptions, day: u32, puzzle: &dyn Puzzle, source: &InputSource) -> Result<usize, String> {
+    let mut lines = source.open(puzzle.example_data())?;
+    let (result, elapsed) = bench::time(|| puzzle.stream(&mut lines));
+    if let Some(e) = lines.take_error() {
+        return Err(format!("could not read {}: {}", source.name(), e));
+    }
+    let (one, two) = result
+        .ok_or(format!("day {} can not stream its input", day))?
+        .map_err(|e| format!("{}:{}", source.name(), e))?;
+
+    if opts.format == Format::Text {
+        println!("Day {}", day);
+    }
+    let mut unsolved = 0;
+    for part in &opts.parts {
+        let ans = match part {
+            Part::One => &one,
+            Part::Two => &two,
+        };
+        if !report(opts, day, *part, ans, elapsed) {
+            unsolved += 1;
+        }
+    }
+
+    Ok(unsolved)
+}
+
+fn run_bench(opts: &BenchOptions) -> Result<(), String> {
+    let mut rows: Vec<bench::Row> = Vec::new();
+    for day in &opts.days {
+        let solver = days::find(*day).ok_or(format!("day {} is not implemented", day))?;
+        let puzzle = (solver.solution)();
+        let source = InputSource::File(input_path(*day));
+        let lines = source.read_lines(puzzle.example_data())?;
+        let day_rows = bench::bench_day(*day, &*puzzle, &lines, &opts.parts, opts.runs)
+            .map_err(|e| format!("{}:{}", source.name(), e))?;
+        rows.extend(day_rows);
+    }
+
+    if opts.csv {
+        print!("{}", bench::format_csv(&rows));
+    } else {
+        print!("{}", bench::format_table(&rows));
+    }
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct Tally {
+    passed: usize,
+    failed: usize,
+    missing: usize,
+}
+
+fn verify_day(day: u32, record: bool, tally: &mut Tally) -> Result<(), String> {
+    let solver = days::find(day).ok_or(format!("day {} is not implemented", day))?;
+    let puzzle = (solver.solution)();
+    let path = answers_path(day);
+    let mut answers = Answers::load(&path)?;
+    let source = InputSource::File(input_path(day));
+    let lines = source.read_lines(puzzle.example_data())?;
+    let input = puzzle
+        .parse_input(&lines)
+        .map_err(|e| format!("{}:{}", source.name(), e))?;
+
+    let mut recorded = false;
+    for part in Part::BOTH {
+        let ans = puzzle.solve(&*input, part);
+        recorded |= check_answer(&format!("Day {:>2} star {}", day, part), &mut answers, part, ans, record, tally);
+    }
+
+    if recorded {
+        answers.save(&path)?;
+    }
+
+    Ok(())
+}
+
+/// Compares `ans` with the known answer and prints the outcome after `label`. Returns
+/// whether `ans` was recorded in `answers` because there was no known answer.
+fn check_answer(label: &str, answers: &mut Answers, part: Part, ans: Answer, record: bool, tally: &mut Tally) -> bool {
+    match answers.get(part) {
+        Some(expected) if ans.is_solved() && expected.to_string() == ans.to_string() => {
+            println!("{}: PASS", label);
+            tally.passed += 1;
+        }
+        Some(expected) => {
+            println!("{}: FAIL", label);
+            println!("  expected: {}", expected.to_string().replace('\n', "\n            "));
+            if ans.is_solved() {
+                println!("  got:      {}", ans.to_string().replace('\n', "\n            "));
+            } else {
+                println!("  not solved: {}", ans.to_string().replace('\n', "\n              "));
+            }
+            tally.failed += 1;
+        }
+        // Not an answer, so there is nothing to record either
+        None if !ans.is_solved() => {
+            println!("{}: MISSING, not solved", label);
+            tally.missing += 1;
+        }
+        None if record => {
+            println!("{}: MISSING, recorded", label);
+            answers.set(part, ans);
+            tally.missing += 1;
+            return true;
+        }
+        None => {
+            println!("{}: MISSING", label);
+            tally.missing += 1;
+        }
+    }
+    false
+}
+
+fn verify_example(example: &examples::Example, record: bool, tally: &mut Tally) -> Result<(), String> {
+    let mut answers = examples::load_answers(example)?;
+    let mut recorded = false;
+    for (part, ans) in example.solve(&Part::BOTH)? {
+        let label = format!("Day {:>2} {} star {}", example.day, example.name, part);
+        recorded |= check_answer(&label, &mut answers, part, ans, record, tally);
+    }
+
+    if recorded {
+        answers.save(&example.answers_path().to_string_lossy())?;
+    }
+
+    Ok(())
+}
+
+fn verify(opts: &VerifyOptions) -> Result<(), String> {
+    let mut tally = Tally::default();
+    if opts.examples {
+        for example in examples::discover(Path::new(examples::EXAMPLES_DIR), &opts.days)? {
+            if let Err(e) = verify_example(&example, opts.record, &mut tally) {
+                println!("Day {:>2} {}: FAIL", example.day, example.name);
+                println!("  {}", e);
+                tally.failed += 1;
+            }
+        }
+    } else {
+        for day in &opts.days {
+            if let Err(e) = verify_day(*day, opts.record, &mut tally) {
+                println!("Day {:>2}: FAIL", day);
+                println!("  {}", e);
+                tally.failed += 1;
+            }
+        }
+    }
+
+    println!("{} passed, {} failed, {} missing", tally.passed, tally.failed, tally.missing);
+    if tally.failed > 0 {
+        return Err(format!("{} answers did not verify", tally.failed));
+    }
+
+    Ok(())
+}
+
+fn run_fuzz(opts: &FuzzOptions) -> Result<(), String> {
+    let mut rng = Rng::new(opts.seed);
+    let mut total = 0;
+    for day in &opts.days {
+        let solver = days::find(*day).ok_or(format!("day {} is not implemented", day))?;
+        let puzzle = (solver.solution)();
+        let corpus = fuzz::load_corpus(*day, puzzle.example_data())?;
+        let crashes = fuzz::fuzz_day(&*puzzle, &corpus, opts.runs, &mut rng);
+        let paths = fuzz::save_crashes(*day, &crashes)?;
+
+        println!("Day {}: {} inputs, {} distinct panics", day, corpus.len() + opts.runs, crashes.len());
+        for (crash, path) in crashes.iter().zip(&paths) {
+            println!("  {} ({}x), smallest input in {}", crash.message, crash.count, path);
+        }
+        total += crashes.len();
+    }
+
+    if total > 0 {
+        return Err(format!("{} distinct panics found", total));
+    }
+    Ok(())
+}
+
+fn run_gen(opts: &GenOptions) -> Result<(), String> {
+    let solver = days::find(opts.day).ok_or(format!("day {} is not implemented", opts.day))?;
+    let input = (solver.solution)()
+        .generate_input(&mut Rng::new(opts.seed), opts.size)
+        .ok_or(format!("day {} has no input generator", opts.day))?;
+    print!("{}", input);
+    Ok(())
+}
+
+fn run_repl(opts: &ReplOptions) -> Result<(), String> {
+    let solver = days::find(opts.day).ok_or(format!("day {} is not implemented", opts.day))?;
+    let puzzle = (solver.solution)();
+    let source = match &opts.input {
+        Some(source) => source.clone(),
+        None => InputSource::File(input_path(opts.day)),
+    };
+    let lines = source.read_lines(puzzle.example_data())?;
+    let input = puzzle.parse_input(&lines).map_err(|e| format!("{}:{}", source.name(), e))?;
+
+    let stdin = io::stdin();
+    let prompt = stdin.is_terminal();
+    repl::run(opts.day, &*puzzle, &*input, stdin.lock(), &mut io::stdout(), prompt)
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let cmd = match cli::parse_args(&args) {
+        Ok(cmd) => cmd,
+        Err(e) => {
+            eprintln!("aoc: {}", e);
+            eprintln!("{}", cli::USAGE);
+            process::exit(2);
+        }
+    };
+
+    let result = match cmd {
+        Command::Run(opts) => run(&opts),
+        Command::Bench(opts) => run_bench(&opts),
+        Command::Verify(opts) => verify(&opts),
+        Command::New(opts) => scaffold::new_day(&opts),
+        Command::Fuzz(opts) => run_fuzz(&opts),
+        Command::Gen(opts) => run_gen(&opts),
+        Command::Repl(opts) => run_repl(&opts),
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
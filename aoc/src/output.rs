@@ -0,0 +1,198 @@
+use std::time::Duration;
+
+use common::{Answer, Part};
+
+use crate::bench::format_duration;
+
+/// How `aoc run` prints answers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// `Star one: 1234` lines under a `Day N` header
+    Text,
+    /// One JSON object per line: `{"day":13,"part":2,"answer":"...","elapsed_ns":1234}`
+    Json,
+    /// `day,part,answer,elapsed_ns` with a header line
+    Csv,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Result<Format, String> {
+        match name {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("invalid format '{}', expected text, json or csv", name)),
+        }
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut out = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            '\r' => out += "\\r",
+            '\t' => out += "\\t",
+            c if (c as u32) < 0x20 => out += &format!("\\u{:04x}", c as u32),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Line to print before the first record, if the format has one.
+pub fn header(format: Format) -> Option<&'static str> {
+    match format {
+        Format::Csv => Some("day,part,answer,elapsed_ns"),
+        Format::Text | Format::Json => None,
+    }
+}
+
+/// One answer as a JSON or CSV record; answers are always strings, so big numbers and
+/// pictures survive unchanged.
+pub fn record(format: Format, day: u32, part: Part, ans: &Answer, elapsed: Duration) -> String {
+    let part = match part {
+        Part::One => 1,
+        Part::Two => 2,
+    };
+    let answer = ans.to_string();
+    match format {
+        Format::Json => format!(
+            "{{\"day\":{},\"part\":{},\"answer\":{},\"elapsed_ns\":{}}}",
+            day,
+            part,
+            json_string(&answer),
+            elapsed.as_nanos()
+        ),
+        Format::Csv => format!("{},{},{},{}", day, part, csv_field(&answer), elapsed.as_nanos()),
+        Format::Text => panic!("Text output has no records"),
+    }
+}
+
+/// How a stage of a day went when running several days at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Parsed,
+    Solved(Answer),
+    /// The input could not be read or parsed, the stage panicked or the part could not
+    /// be solved
+    Failed(String),
+}
+
+/// One stage of a day: parsing (`part` is `None`) or one of the parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageResult {
+    pub day: u32,
+    pub part: Option<Part>,
+    pub outcome: Outcome,
+    pub elapsed: Duration,
+}
+
+impl StageResult {
+    pub fn stage(&self) -> String {
+        match self.part {
+            Some(part) => format!("star {}", part),
+            None => String::from("parse"),
+        }
+    }
+}
+
+/// A table with a line per stage, followed by a line with the totals. Pictures are
+/// shown below the line of their part.
+pub fn summary(results: &[StageResult], wall_time: Duration, threads: usize) -> String {
+    let mut out = format!("{:>3}  {:<8}  {:>8}  {}\n", "Day", "Stage", "Time", "Answer");
+    for result in results {
+        let answer = match &result.outcome {
+            Outcome::Parsed => String::new(),
+            Outcome::Solved(ans) => ans.to_string().replace('\n', &format!("\n{:25}", "")),
+            Outcome::Failed(e) => format!("FAILED: {}", e).replace('\n', &format!("\n{:25}", "")),
+        };
+        let line = format!("{:>3}  {:<8}  {:>8}  {}", result.day, result.stage(), format_duration(result.elapsed), answer);
+        out += line.trim_end();
+        out.push('\n');
+    }
+
+    let days = results.iter().filter(|r| r.part.is_none()).count();
+    let answers = results.iter().filter(|r| matches!(r.outcome, Outcome::Solved(_))).count();
+    let failed = results.iter().filter(|r| matches!(r.outcome, Outcome::Failed(_))).count();
+    let work: Duration = results.iter().map(|r| r.elapsed).sum();
+    out += &format!(
+        "{} days, {} answers, {} failed in {} on {} thread{} ({} of work)\n",
+        days,
+        answers,
+        failed,
+        format_duration(wall_time),
+        threads,
+        if threads == 1 { "" } else { "s" },
+        format_duration(work)
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use common::{Answer, Part};
+
+    use super::{Format, Outcome, StageResult};
+
+    #[test]
+    fn json() {
+        let ans = Answer::from("#.\n.#\n");
+        assert_eq!(
+            super::record(Format::Json, 13, Part::Two, &ans, Duration::from_nanos(1234)),
+            "{\"day\":13,\"part\":2,\"answer\":\"#.\\n.#\",\"elapsed_ns\":1234}"
+        );
+        assert_eq!(super::json_string("a\"b\\c\u{1}"), "\"a\\\"b\\\\c\\u0001\"");
+    }
+
+    #[test]
+    fn csv() {
+        let ans = Answer::from(1521usize);
+        assert_eq!(super::record(Format::Csv, 1, Part::One, &ans, Duration::from_nanos(5)), "1,1,1521,5");
+        let ans = Answer::from("a,\"b\"\nc");
+        assert_eq!(
+            super::record(Format::Csv, 13, Part::Two, &ans, Duration::from_nanos(5)),
+            "13,2,\"a,\"\"b\"\"\nc\",5"
+        );
+    }
+
+    #[test]
+    fn summary() {
+        let ms = Duration::from_millis;
+        let results = vec![
+            StageResult { day: 10, part: None, outcome: Outcome::Parsed, elapsed: ms(1) },
+            StageResult { day: 10, part: Some(Part::One), outcome: Outcome::Solved(Answer::from(26397usize)), elapsed: ms(2) },
+            StageResult { day: 10, part: Some(Part::Two), outcome: Outcome::Failed(String::from("OK line found")), elapsed: ms(3) },
+            StageResult { day: 13, part: None, outcome: Outcome::Parsed, elapsed: ms(4) },
+            StageResult { day: 13, part: Some(Part::One), outcome: Outcome::Failed(String::from("not letters\n##")), elapsed: ms(1) },
+            StageResult { day: 13, part: Some(Part::Two), outcome: Outcome::Solved(Answer::from("#.\n.#\n")), elapsed: ms(5) },
+        ];
+        assert_eq!(
+            super::summary(&results, ms(12), 4),
+            "Day  Stage         Time  Answer
+ 10  parse       1.00ms
+ 10  star one    2.00ms  26397
+ 10  star two    3.00ms  FAILED: OK line found
+ 13  parse       4.00ms
+ 13  star one    1.00ms  FAILED: not letters
+                         ##
+ 13  star two    5.00ms  #.
+                         .#
+2 days, 2 answers, 2 failed in 12.0ms on 4 threads (16.0ms of work)
+"
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! Turning panics into errors, for running code that may panic on bad input without
+//! taking the whole runner down.
+
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+
+thread_local! {
+    /// Message of the last panic on this thread, set by the hook of `quietly`.
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` with a panic hook that records the message and location of panics instead
+/// of printing them, so `catch` can report them.
+pub fn quietly<T, F: FnOnce() -> T>(f: F) -> T {
+    let old_hook = panic::take_hook();
+    panic::set_hook(Box::new(|info| {
+        let location = info.location().map(|l| format!("{}:{}", l.file(), l.line())).unwrap_or_default();
+        let payload = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        LAST_PANIC.with(|last| *last.borrow_mut() = Some(format!("{}: {}", location, payload)));
+    }));
+    let result = f();
+    panic::set_hook(old_hook);
+    result
+}
+
+/// Runs `f`, returning the panic message as error if it panics. The message includes
+/// the location of the panic when running under `quietly`.
+pub fn catch<T, F: FnOnce() -> T>(f: F) -> Result<T, String> {
+    LAST_PANIC.with(|last| last.borrow_mut().take());
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .map_err(|_| LAST_PANIC.with(|last| last.borrow_mut().take()).unwrap_or_else(|| String::from("unknown panic")))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn catch() {
+        let result = super::quietly(|| {
+            assert_eq!(super::catch(|| 1 + 1), Ok(2));
+            super::catch(|| -> u32 { panic!("OK line found, not expected") })
+        });
+        let err = result.unwrap_err();
+        assert!(err.starts_with("aoc/src/panics.rs:"), "{}", err);
+        assert!(err.ends_with(": OK line found, not expected"), "{}", err);
+    }
+}
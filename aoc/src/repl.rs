@@ -0,0 +1,127 @@
+//! `aoc repl`: explores the parsed input of a day with the commands of its session, a
+//! command per line. Besides the commands of the day there are `show`, `reset`, `help`
+//! and `quit`.
+
+use std::any::Any;
+use std::io::{BufRead, Write};
+
+use common::repl::{self, Command, Session};
+use common::Puzzle;
+
+use crate::panics;
+
+static BUILTIN: &[Command] = &[
+    Command { name: "show", args: "", help: "shows the current state" },
+    Command { name: "reset", args: "", help: "starts over from the input" },
+    Command { name: "help", args: "", help: "lists the commands" },
+    Command { name: "quit", args: "", help: "leaves the repl, like the end of the input does" },
+];
+
+/// Runs the command on `line`; returns what to print.
+fn execute(puzzle: &dyn Puzzle, input: &dyn Any, session: &mut Box<dyn Session>, line: &str) -> Result<String, String> {
+    let (name, args) = match line.trim().split_once(char::is_whitespace) {
+        Some((name, args)) => (name, args.trim()),
+        None => (line.trim(), ""),
+    };
+    match name {
+        "" => Ok(String::new()),
+        "show" => Ok(session.show()),
+        "reset" => {
+            *session = puzzle.start_session(input).expect("The day had a session before");
+            Ok(session.show())
+        }
+        "help" => Ok(repl::describe(session.commands()) + &repl::describe(BUILTIN)),
+        // A command that panics on odd arguments should not end the session
+        name if session.commands().iter().any(|c| c.name == name) => {
+            panics::quietly(|| panics::catch(|| session.run(name, args)))?
+        }
+        _ => Err(format!("unknown command '{}', try help", name)),
+    }
+}
+
+/// Reads commands for day `day` from `commands` until `quit` or the end, and writes
+/// their output to `out`. With `prompt` every command is asked for.
+pub fn run<R: BufRead, W: Write>(
+    day: u32,
+    puzzle: &dyn Puzzle,
+    input: &dyn Any,
+    mut commands: R,
+    out: &mut W,
+    prompt: bool,
+) -> Result<(), String> {
+    let mut session = puzzle.start_session(input).ok_or(format!("day {} has no repl commands", day))?;
+    let write_error = |e: std::io::Error| format!("could not write the output: {}", e);
+
+    if prompt {
+        writeln!(out, "Day {}, help lists the commands", day).map_err(write_error)?;
+    }
+    let mut line = String::new();
+    loop {
+        if prompt {
+            write!(out, "aoc> ").map_err(write_error)?;
+            out.flush().map_err(write_error)?;
+        }
+        line.clear();
+        if commands.read_line(&mut line).map_err(|e| format!("could not read the commands: {}", e))? == 0 {
+            if prompt {
+                writeln!(out).map_err(write_error)?;
+            }
+            return Ok(());
+        }
+        if matches!(line.trim(), "quit" | "exit") {
+            return Ok(());
+        }
+
+        match execute(puzzle, input, &mut session, &line) {
+            Ok(text) if text.is_empty() || text.ends_with('\n') => write!(out, "{}", text),
+            Ok(text) => writeln!(out, "{}", text),
+            Err(e) => writeln!(out, "error: {}", e),
+        }
+        .map_err(write_error)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::days;
+
+    /// Output of running `script` on the example of `day`.
+    fn run(day: u32, script: &str) -> Result<String, String> {
+        let puzzle = (days::find(day).unwrap().solution)();
+        let lines: Vec<String> = puzzle.example_data().lines().map(|x| x.to_string()).collect();
+        let input = puzzle.parse_input(&lines).unwrap();
+        let mut out = Vec::new();
+        super::run(day, &*puzzle, &*input, Cursor::new(script), &mut out, false)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn script() {
+        let out = run(14, "gen 10\n\nshow\nreset\nfly\ngen x\nquit\ngen\n").unwrap();
+        let lines: Vec<&str> = out.lines().filter(|line| line.starts_with("Step") || line.starts_with("error")).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "Step 10: length 3073, 1749 - 161 = 1588",
+                "Step 10: length 3073, 1749 - 161 = 1588",
+                "Step 0: length 4, 2 - 1 = 1",
+                "error: unknown command 'fly', try help",
+                "error: invalid value 'x' for gen, expected a number from 1 to 10000",
+            ]
+        );
+    }
+
+    #[test]
+    fn help() {
+        let out = run(13, "help").unwrap();
+        assert!(out.starts_with("  fold x=N|y=N"), "{}", out);
+        assert!(out.contains("\n  reset "), "{}", out);
+    }
+
+    #[test]
+    fn without_commands() {
+        assert_eq!(run(1, "show"), Err(String::from("day 1 has no repl commands")));
+    }
+}
@@ -0,0 +1,235 @@
+use std::fs;
+use std::path::Path;
+
+use common::{Answer, Answers, Part};
+
+use crate::cli::NewOptions;
+use crate::examples::EXAMPLES_DIR;
+
+static TEMPLATE_CARGO: &str = include_str!("../../template/Cargo.toml");
+static TEMPLATE_LIB: &str = include_str!("../../template/lib.rs");
+static TEMPLATE_MAIN: &str = include_str!("../../template/main.rs");
+
+fn day_name(day: u32) -> String {
+    format!("day{:02}", day)
+}
+
+/// The return type and placeholder body of a star whose example answer is `expect`,
+/// and the literal to compare its answer with.
+fn star_signature(expect: Option<&str>) -> Result<(&'static str, &'static str, String), String> {
+    let expect = match expect {
+        None => return Ok(("usize", "0", String::from("0"))),
+        Some(expect) => expect,
+    };
+    if expect.parse::<usize>().is_ok() {
+        Ok(("usize", "0", expect.to_string()))
+    } else if expect.parse::<i64>().is_ok() {
+        Ok(("i64", "0", expect.to_string()))
+    } else if expect.parse::<i128>().is_ok() {
+        Err(format!("expected answer {} does not fit in 64 bits", expect))
+    } else {
+        Ok(("String", "String::new()", format!("{:?}", expect)))
+    }
+}
+
+/// Fills in `template/lib.rs` for `day`, with the expected test answers. A star with a
+/// text answer returns a `String` instead of a number.
+pub fn render_lib(day: u32, expect1: Option<&str>, expect2: Option<&str>) -> Result<String, String> {
+    let mut lib = TEMPLATE_LIB
+        .replace("dayNN", &day_name(day))
+        .replace("DayNN", &format!("Day{:02}", day))
+        .replace("Day NN", &format!("Day {}", day));
+
+    let mut literals = Vec::new();
+    for (star, expect) in [("star_one", expect1), ("star_two", expect2)] {
+        let (ty, body, literal) = star_signature(expect)?;
+        let placeholder = format!("pub fn {}(_lines: &[String]) -> usize {{\n    0\n}}", star);
+        assert!(lib.contains(&placeholder), "Template needs a placeholder {}", star);
+        lib = lib.replace(&placeholder, &format!("pub fn {}(_lines: &[String]) -> {} {{\n    {}\n}}", star, ty, body));
+        literals.push(literal);
+    }
+
+    // The first placeholder assertion is the one of star one, the second of star two
+    let pieces: Vec<&str> = lib.split("assert_eq!(ans, 0);").collect();
+    assert_eq!(pieces.len(), 3, "Template needs one assertion per star");
+    let assertion = |literal: &str| format!("assert_eq!(ans, {});", literal);
+    Ok(format!("{}{}{}{}{}", pieces[0], assertion(&literals[0]), pieces[1], assertion(&literals[1]), pieces[2]))
+}
+
+/// The answers file of the example, with the expected answers that are known.
+pub fn render_example_answers(expect1: Option<&str>, expect2: Option<&str>) -> String {
+    let mut answers = Answers::default();
+    for (part, expect) in [(Part::One, expect1), (Part::Two, expect2)] {
+        if let Some(expect) = expect {
+            answers.set(part, expect.parse::<i128>().map_or_else(|_| Answer::Text(expect.to_string()), Answer::Number));
+        }
+    }
+    answers.to_toml()
+}
+
+/// Inserts `entry` into the list of lines that start with `prefix` followed by a day
+/// number, keeping the list sorted by day.
+pub fn insert_sorted(text: &str, prefix: &str, day: u32, entry: &str) -> Result<String, String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+
+    let entries: Vec<(usize, u32)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let rest = line.trim_start().strip_prefix(prefix)?;
+            let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+            Some((idx, digits.parse::<u32>().ok()?))
+        })
+        .collect();
+
+    if entries.iter().any(|(_, d)| *d == day) {
+        return Err(format!("day {} is already registered", day));
+    }
+    let pos = match entries.iter().rev().find(|(_, d)| *d < day) {
+        Some((idx, _)) => idx + 1,
+        None => entries.first().ok_or(format!("no '{}' entries found", prefix))?.0,
+    };
+
+    lines.insert(pos, entry);
+    let mut result = lines.join("\n");
+    if text.ends_with('\n') {
+        result.push('\n');
+    }
+    Ok(result)
+}
+
+fn update_file(path: &str, prefix: &str, day: u32, entry: &str) -> Result<(), String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("unreadable file {}: {}", path, e))?;
+    let text = insert_sorted(&text, prefix, day, entry).map_err(|e| format!("{}: {}", path, e))?;
+    fs::write(path, text).map_err(|e| format!("could not write {}: {}", path, e))
+}
+
+/// Creates `dayNN/` from the template with its example in `examples/dayNN/`, and
+/// registers it in the workspace and the runner.
+pub fn new_day(opts: &NewOptions) -> Result<(), String> {
+    let name = day_name(opts.day);
+    let example_dir = format!("{}/{}", EXAMPLES_DIR, name);
+    for dir in [&name, &example_dir] {
+        if Path::new(dir).exists() {
+            return Err(format!("{} already exists", dir));
+        }
+    }
+
+    let example = match &opts.example {
+        Some(path) => Some(fs::read_to_string(path).map_err(|e| format!("unreadable example file {}: {}", path, e))?),
+        None => None,
+    };
+    let lib = render_lib(opts.day, opts.expect1.as_deref(), opts.expect2.as_deref())?;
+    let main = TEMPLATE_MAIN
+        .replace("dayNN", &name)
+        .replace("DayNN", &format!("Day{:02}", opts.day));
+    let cargo = TEMPLATE_CARGO.replace("dayNN", &name);
+
+    let write = |path: String, contents: &str| fs::write(&path, contents).map_err(|e| format!("could not write {}: {}", path, e));
+    fs::create_dir_all(format!("{}/src", name)).map_err(|e| format!("could not create {}: {}", name, e))?;
+    write(format!("{}/Cargo.toml", name), &cargo)?;
+    write(format!("{}/src/lib.rs", name), &lib)?;
+    write(format!("{}/src/main.rs", name), &main)?;
+    write(format!("{}/input", name), "")?;
+    fs::create_dir_all(&example_dir).map_err(|e| format!("could not create {}: {}", example_dir, e))?;
+    write(format!("{}/example.txt", example_dir), example.as_deref().unwrap_or(""))?;
+    write(
+        format!("{}/example.toml", example_dir),
+        &render_example_answers(opts.expect1.as_deref(), opts.expect2.as_deref()),
+    )?;
+
+    update_file("Cargo.toml", "\"day", opts.day, &format!("    \"{}\",", name))?;
+    update_file("aoc/Cargo.toml", "day", opts.day, &format!("{} = {{ path = \"../{}\" }}", name, name))?;
+    update_file(
+        "aoc/src/days.rs",
+        "Day { day: ",
+        opts.day,
+        &format!("    Day {{ day: {}, solution: || Box::new({}::Day{:02}) }},", opts.day, name, opts.day),
+    )?;
+
+    println!("Created {}", name);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::process::Command;
+
+    /// Type-checks `lib`, with its tests, as the crate of day `day` next to an empty
+    /// example.
+    fn check_compiles(day: u32, lib: &str) {
+        let name = format!("day{:02}", day);
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}-{}", std::process::id(), name));
+        let common = concat!(env!("CARGO_MANIFEST_DIR"), "/../common");
+        let cargo = format!(
+            "{}\n[workspace]\n",
+            super::TEMPLATE_CARGO.replace("dayNN", &name).replace("../common", common)
+        );
+        fs::create_dir_all(root.join(&name).join("src")).unwrap();
+        fs::create_dir_all(root.join("examples").join(&name)).unwrap();
+        fs::write(root.join(&name).join("Cargo.toml"), cargo).unwrap();
+        fs::write(root.join(&name).join("src/lib.rs"), lib).unwrap();
+        fs::write(root.join("examples").join(&name).join("example.txt"), "").unwrap();
+
+        let output = Command::new(env!("CARGO"))
+            .args(["check", "--tests", "--offline", "--quiet"])
+            .current_dir(root.join(&name))
+            .env("CARGO_TARGET_DIR", root.join("target"))
+            .output()
+            .unwrap();
+        fs::remove_dir_all(&root).unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    #[test]
+    fn render_lib() {
+        let lib = super::render_lib(19, Some("79"), None).unwrap();
+        assert!(lib.starts_with("//! Day 19 of Advent of Code 2021."));
+        assert!(lib.contains("pub struct Day19;"));
+        assert!(lib.contains("impl Solution for Day19 {"));
+        assert!(lib.contains("pub static TEST_DATA: &str = include_str!(\"../../examples/day19/example.txt\");"));
+        assert!(lib.contains("assert_eq!(ans, 79);"));
+        assert!(lib.contains("assert_eq!(ans, 0);"));
+        check_compiles(19, &lib);
+
+        let lib = super::render_lib(20, Some("-10"), Some("#.\n.#")).unwrap();
+        assert!(lib.contains("pub fn star_one(_lines: &[String]) -> i64 {"));
+        assert!(lib.contains("assert_eq!(ans, -10);"));
+        assert!(lib.contains("pub fn star_two(_lines: &[String]) -> String {"));
+        assert!(lib.contains("assert_eq!(ans, \"#.\\n.#\");"));
+        check_compiles(20, &lib);
+
+        assert_eq!(
+            super::render_lib(21, Some("100000000000000000000"), None),
+            Err(String::from("expected answer 100000000000000000000 does not fit in 64 bits"))
+        );
+    }
+
+    #[test]
+    fn render_example_answers() {
+        assert_eq!(super::render_example_answers(Some("79"), None), "star_one = 79\n");
+        assert_eq!(super::render_example_answers(None, Some("CEJKLUGJ")), "star_two = \"CEJKLUGJ\"\n");
+        assert_eq!(super::render_example_answers(None, None), "");
+    }
+
+    #[test]
+    fn insert_sorted() {
+        let text = "[dependencies]\ncommon = {}\nday01 = {}\nday03 = {}\n";
+        assert_eq!(
+            super::insert_sorted(text, "day", 2, "day02 = {}"),
+            Ok(String::from("[dependencies]\ncommon = {}\nday01 = {}\nday02 = {}\nday03 = {}\n"))
+        );
+        assert_eq!(
+            super::insert_sorted(text, "day", 19, "day19 = {}"),
+            Ok(String::from("[dependencies]\ncommon = {}\nday01 = {}\nday03 = {}\nday19 = {}\n"))
+        );
+        assert!(super::insert_sorted(text, "day", 3, "day03 = {}").is_err());
+
+        let days = "pub static DAYS: &[Day] = &[\n    Day { day: 18, solution: x },\n];";
+        assert_eq!(
+            super::insert_sorted(days, "Day { day: ", 19, "    Day { day: 19, solution: y },"),
+            Ok(String::from("pub static DAYS: &[Day] = &[\n    Day { day: 18, solution: x },\n    Day { day: 19, solution: y },\n];"))
+        );
+    }
+}
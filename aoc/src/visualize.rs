@@ -0,0 +1,252 @@
+//! Plays back the frames of a simulation in the terminal. The terminal is put in raw
+//! mode with `stty`, so keys work without pressing enter and no extra crates are
+//! needed; when there is no terminal the frames are printed one after another.
+
+use std::fs::File;
+use std::io::{self, IsTerminal, Read, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use common::visual::Frame;
+
+pub static CONTROLS: &str = "space pause  n/→ step  b/← back  +/- speed  home/end first/last  q quit";
+
+const MIN_DELAY: Duration = Duration::from_millis(10);
+const MAX_DELAY: Duration = Duration::from_millis(2560);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Pause,
+    Next,
+    Previous,
+    Faster,
+    Slower,
+    First,
+    Last,
+    Quit,
+}
+
+/// The keys in a chunk of terminal input; anything else is ignored.
+pub fn parse_keys(input: &[u8]) -> Vec<Key> {
+    let mut keys = Vec::new();
+    let mut idx = 0;
+    while idx < input.len() {
+        let (key, len) = match &input[idx..] {
+            [0x1b, b'[', b'C', ..] => (Some(Key::Next), 3),
+            [0x1b, b'[', b'D', ..] => (Some(Key::Previous), 3),
+            [0x1b, b'[', b'H', ..] => (Some(Key::First), 3),
+            [0x1b, b'[', b'F', ..] => (Some(Key::Last), 3),
+            [b' ', ..] => (Some(Key::Pause), 1),
+            [b'n' | b'l' | b'.', ..] => (Some(Key::Next), 1),
+            [b'b' | b'h' | b',', ..] => (Some(Key::Previous), 1),
+            [b'+' | b'=', ..] => (Some(Key::Faster), 1),
+            [b'-' | b'_', ..] => (Some(Key::Slower), 1),
+            [b'g', ..] => (Some(Key::First), 1),
+            [b'G', ..] => (Some(Key::Last), 1),
+            // Ctrl-C does not interrupt in raw mode, so it quits like 'q'
+            [b'q' | b'Q' | 0x03, ..] => (Some(Key::Quit), 1),
+            _ => (None, 1),
+        };
+        keys.extend(key);
+        idx += len;
+    }
+    keys
+}
+
+/// Where the playback is and how fast it goes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    pub frame: usize,
+    pub frames: usize,
+    pub playing: bool,
+    pub delay: Duration,
+}
+
+impl Player {
+    pub fn new(frames: usize) -> Player {
+        Player { frame: 0, frames, playing: true, delay: Duration::from_millis(160) }
+    }
+
+    /// Handles a key; returns false to quit. Stepping pauses the playback.
+    pub fn press(&mut self, key: Key) -> bool {
+        match key {
+            Key::Pause => {
+                // Playing again from the last frame starts over
+                if !self.playing && self.frame + 1 == self.frames {
+                    self.frame = 0;
+                }
+                self.playing = !self.playing;
+            }
+            Key::Next => {
+                self.playing = false;
+                self.frame = (self.frame + 1).min(self.frames - 1);
+            }
+            Key::Previous => {
+                self.playing = false;
+                self.frame = self.frame.saturating_sub(1);
+            }
+            Key::Faster => self.delay = (self.delay / 2).max(MIN_DELAY),
+            Key::Slower => self.delay = (self.delay * 2).min(MAX_DELAY),
+            Key::First => self.frame = 0,
+            Key::Last => self.frame = self.frames - 1,
+            Key::Quit => return false,
+        }
+        true
+    }
+
+    /// Moves on to the next frame when playing; stops at the last one.
+    pub fn tick(&mut self) {
+        if self.playing {
+            if self.frame + 1 < self.frames {
+                self.frame += 1;
+            } else {
+                self.playing = false;
+            }
+        }
+    }
+}
+
+/// Runs `stty` on the terminal, returning its output.
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(File::open("/dev/tty")?)
+        .stderr(Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other("stty failed"));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Raw mode with non-blocking reads, restored to the saved settings when dropped.
+struct RawMode {
+    saved: String,
+}
+
+impl RawMode {
+    fn enable() -> io::Result<RawMode> {
+        let saved = stty(&["-g"])?;
+        stty(&["-icanon", "-echo", "-isig", "min", "0", "time", "0"])?;
+        // Alternate screen, cursor hidden
+        print!("\x1b[?1049h\x1b[?25l");
+        Ok(RawMode { saved })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = io::stdout().flush();
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+fn draw(out: &mut impl Write, frames: &[Frame], player: &Player) -> io::Result<()> {
+    let frame = &frames[player.frame];
+    let state = if player.playing { "playing" } else { "paused" };
+    write!(out, "\x1b[H\x1b[2J{}\r\n\r\n", frame.caption)?;
+    for line in frame.text.lines() {
+        write!(out, "{}\r\n", line)?;
+    }
+    write!(
+        out,
+        "\r\nFrame {}/{}, {}, {} ms per frame\r\n{}",
+        player.frame + 1,
+        frames.len(),
+        state,
+        player.delay.as_millis(),
+        CONTROLS
+    )?;
+    out.flush()
+}
+
+/// Shows the frames until the user quits, or prints them all if there is no terminal.
+pub fn play(frames: &[Frame]) -> Result<(), String> {
+    if frames.is_empty() {
+        return Ok(());
+    }
+    let tty = if io::stdout().is_terminal() { File::open("/dev/tty").ok() } else { None };
+    let (mut keys, raw) = match tty.and_then(|tty| RawMode::enable().ok().map(|raw| (tty, raw))) {
+        Some(terminal) => terminal,
+        None => {
+            for frame in frames {
+                println!("{}\n{}", frame.caption, frame.text);
+            }
+            return Ok(());
+        }
+    };
+
+    let mut out = io::stdout().lock();
+    let mut player = Player::new(frames.len());
+    let mut shown = None;
+    let mut last_tick = Instant::now();
+    let mut buf = [0u8; 64];
+    loop {
+        let len = keys.read(&mut buf).map_err(|e| format!("could not read the terminal: {}", e))?;
+        let mut quit = false;
+        for key in parse_keys(&buf[..len]) {
+            quit |= !player.press(key);
+        }
+        if quit {
+            break;
+        }
+        if player.playing && last_tick.elapsed() >= player.delay {
+            player.tick();
+            last_tick = Instant::now();
+        }
+        if shown.as_ref() != Some(&player) {
+            draw(&mut out, frames, &player).map_err(|e| format!("could not draw: {}", e))?;
+            shown = Some(player.clone());
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+
+    drop(out);
+    drop(raw);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Key, Player};
+
+    #[test]
+    fn parse_keys() {
+        assert_eq!(super::parse_keys(b" n\x1b[Cb\x1b[D+-q"), vec![Key::Pause, Key::Next, Key::Next, Key::Previous, Key::Previous, Key::Faster, Key::Slower, Key::Quit]);
+        assert_eq!(super::parse_keys(b"xy\x1b"), vec![]);
+        assert_eq!(super::parse_keys(b"\x03"), vec![Key::Quit]);
+    }
+
+    #[test]
+    fn player() {
+        let mut player = Player::new(3);
+        player.tick();
+        assert_eq!(player.frame, 1);
+        player.tick();
+        player.tick();
+        assert_eq!((player.frame, player.playing), (2, false));
+
+        // Playing from the end starts over
+        assert!(player.press(Key::Pause));
+        assert_eq!((player.frame, player.playing), (0, true));
+
+        player.press(Key::Previous);
+        assert_eq!((player.frame, player.playing), (0, false));
+        player.press(Key::Next);
+        player.press(Key::Next);
+        player.press(Key::Next);
+        assert_eq!(player.frame, 2);
+
+        for _ in 0..20 {
+            player.press(Key::Faster);
+        }
+        assert_eq!(player.delay, super::MIN_DELAY);
+        player.press(Key::Slower);
+        assert_eq!(player.delay, Duration::from_millis(20));
+        assert!(!player.press(Key::Quit));
+    }
+}
@@ -0,0 +1,241 @@
+use std::fs;
+use std::io;
+
+use crate::error::AocError;
+use crate::solution::{Answer, Part};
+
+/// Known-good answers of a day, stored as `answers.toml` next to its input:
+///
+/// ```toml
+/// star_one = 807
+/// star_two = '''
+/// #....
+/// '''
+/// ```
+///
+/// Only the small part of TOML needed for that is supported: integers, basic strings
+/// and multi-line literal strings, one `key = value` per line, and `#` comments.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub star_one: Option<Answer>,
+    pub star_two: Option<Answer>,
+}
+
+impl Answers {
+    pub fn get(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.star_one.as_ref(),
+            Part::Two => self.star_two.as_ref(),
+        }
+    }
+
+    pub fn set(&mut self, part: Part, answer: Answer) {
+        match part {
+            Part::One => self.star_one = Some(answer),
+            Part::Two => self.star_two = Some(answer),
+        }
+    }
+
+    /// Reads answers from `path`; a missing file means no answers are known yet.
+    pub fn load(path: &str) -> Result<Answers, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => Answers::parse(&text).map_err(|e| format!("{}:{}", path, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(format!("unreadable answers file {}: {}", path, e)),
+        }
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        fs::write(path, self.to_toml()).map_err(|e| format!("could not write {}: {}", path, e))
+    }
+
+    pub fn parse(text: &str) -> Result<Answers, AocError> {
+        let mut answers = Answers::default();
+
+        let lines: Vec<&str> = text.lines().collect();
+        let mut idx = 0;
+        while idx < lines.len() {
+            let line_nr = idx + 1;
+            let line = lines[idx];
+            idx += 1;
+
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = trimmed
+                .split_once('=')
+                .ok_or_else(|| AocError::at_end(line_nr, line, "'key = value'"))?;
+            let key = key.trim();
+            let value = value.trim();
+            let part = match key {
+                "star_one" => Part::One,
+                "star_two" => Part::Two,
+                _ => return Err(AocError::at(line_nr, line, key, "star_one or star_two")),
+            };
+
+            let answer = if let Some(rest) = value.strip_prefix("'''") {
+                let mut text = String::new();
+                let mut rest = rest;
+                loop {
+                    if let Some(end) = rest.find("'''") {
+                        text += &rest[..end];
+                        break;
+                    }
+                    // The newline directly after the opening quotes is not part of the string
+                    if !(text.is_empty() && rest.is_empty() && idx == line_nr) {
+                        text += rest;
+                        text.push('\n');
+                    }
+                    rest = lines
+                        .get(idx)
+                        .copied()
+                        .ok_or_else(|| AocError::at_end(line_nr, line, "closing '''"))?;
+                    idx += 1;
+                }
+                Answer::Text(text)
+            } else if let Some(rest) = value.strip_prefix('"') {
+                Answer::Text(parse_basic_string(line_nr, line, rest)?)
+            } else {
+                let digits: String = value.chars().filter(|c| *c != '_').collect();
+                let num = digits
+                    .parse::<i128>()
+                    .map_err(|_| AocError::at(line_nr, line, value, "an integer or a string"))?;
+                Answer::Number(num)
+            };
+            answers.set(part, answer);
+        }
+
+        Ok(answers)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut out = String::new();
+        for (key, answer) in [("star_one", &self.star_one), ("star_two", &self.star_two)] {
+            match answer {
+                // Not an answer, so there is nothing to record
+                None | Some(Answer::Unsolved(_)) => (),
+                Some(Answer::Number(num)) => out += &format!("{} = {}\n", key, num),
+                // Literal strings have no escapes for ''' and control characters other than tabs
+                Some(Answer::Text(text)) if text.contains('\n') && !text.contains("'''") && !text.contains(is_escaped) => {
+                    out += &format!("{} = '''\n{}\n'''\n", key, text.trim_end())
+                }
+                Some(Answer::Text(text)) => out += &format!("{} = \"{}\"\n", key, escape(text)),
+            }
+        }
+        out
+    }
+}
+
+/// Whether `c` needs an escape sequence in a `"..."` string, other than a newline.
+fn is_escaped(c: char) -> bool {
+    c != '\n' && c != '\t' && c.is_control()
+}
+
+/// `text` as the inside of a `"..."` string, with only the escapes `parse_basic_string`
+/// reads.
+fn escape(text: &str) -> String {
+    let mut out = String::new();
+    for c in text.chars() {
+        match c {
+            '\\' => out += "\\\\",
+            '"' => out += "\\\"",
+            '\n' => out += "\\n",
+            '\t' => out += "\\t",
+            c if is_escaped(c) => out += &format!("\\u{:04X}", c as u32),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Parses the rest of a `"..."` string, after the opening quote.
+fn parse_basic_string(line_nr: usize, line: &str, rest: &str) -> Result<String, AocError> {
+    let mut text = String::new();
+    let mut chars = rest.char_indices();
+    while let Some((pos, c)) = chars.next() {
+        match c {
+            '"' => {
+                let trailing = rest[pos + 1..].trim();
+                if !trailing.is_empty() && !trailing.starts_with('#') {
+                    return Err(AocError::at(line_nr, line, trailing, "end of line"));
+                }
+                return Ok(text);
+            }
+            '\\' => match chars.next() {
+                Some((_, 'n')) => text.push('\n'),
+                Some((_, 't')) => text.push('\t'),
+                Some((_, '"')) => text.push('"'),
+                Some((_, '\\')) => text.push('\\'),
+                Some((_, '\'')) => text.push('\''),
+                Some((start, 'u')) => {
+                    let hex = rest.get(start + 1..start + 5).unwrap_or("");
+                    let c = u32::from_str_radix(hex, 16)
+                        .ok()
+                        .filter(|_| hex.chars().all(|c| c.is_ascii_hexdigit()))
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| AocError::at_first_char(line_nr, line, &rest[pos..], "an escape sequence"))?;
+                    text.push(c);
+                    chars.nth(3);
+                }
+                _ => return Err(AocError::at_first_char(line_nr, line, &rest[pos..], "an escape sequence")),
+            },
+            c => text.push(c),
+        }
+    }
+    Err(AocError::at_end(line_nr, line, "closing '\"'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Answers;
+    use crate::solution::{Answer, Part};
+
+    #[test]
+    fn parse() {
+        let answers = Answers::parse("# day 13\nstar_one = 807\nstar_two = '''\n#..#\n.##.\n'''\n").unwrap();
+        assert_eq!(answers.get(Part::One), Some(&Answer::Number(807)));
+        assert_eq!(answers.get(Part::Two), Some(&Answer::Text(String::from("#..#\n.##.\n"))));
+
+        let answers = Answers::parse("star_two = \"a \\\"b\\\"\"").unwrap();
+        assert_eq!(answers.star_one, None);
+        assert_eq!(answers.star_two, Some(Answer::Text(String::from("a \"b\""))));
+
+        assert_eq!(Answers::parse("star_one = 1_000").unwrap().star_one, Some(Answer::Number(1000)));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = Answers::parse("star_three = 1").unwrap_err();
+        assert_eq!(err.to_string(), "1:1: expected star_one or star_two, found 'star_three'");
+
+        let err = Answers::parse("\nstar_one = 12x").unwrap_err();
+        assert_eq!(err.to_string(), "2:12: expected an integer or a string, found '12x'");
+
+        let err = Answers::parse("star_one = \"abc").unwrap_err();
+        assert_eq!(err.to_string(), "1:16: expected closing '\"'");
+
+        let err = Answers::parse("star_one = \"a\\u12\"").unwrap_err();
+        assert_eq!(err.to_string(), "1:14: expected an escape sequence, found '\\'");
+
+        let err = Answers::parse("star_two = '''\n#..#").unwrap_err();
+        assert_eq!(err.to_string(), "1:15: expected closing '''");
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut answers = Answers::default();
+        answers.set(Part::One, Answer::Number(-3));
+        answers.set(Part::Two, Answer::Text(String::from("#.\n.#\n")));
+        assert_eq!(answers.to_toml(), "star_one = -3\nstar_two = '''\n#.\n.#\n'''\n");
+        assert_eq!(Answers::parse(&answers.to_toml()).unwrap(), answers);
+
+        answers.set(Part::Two, Answer::Text(String::from("say \"hi\"")));
+        assert_eq!(Answers::parse(&answers.to_toml()).unwrap(), answers);
+
+        answers.set(Part::Two, Answer::Text(String::from("Grüße\\\t\u{7}x\r\n.#")));
+        assert_eq!(answers.to_toml(), "star_one = -3\nstar_two = \"Grüße\\\\\\t\\u0007x\\u000D\\n.#\"\n");
+        assert_eq!(Answers::parse(&answers.to_toml()).unwrap(), answers);
+    }
+}
@@ -0,0 +1,255 @@
+//! Unsigned integers of any size, for counts that grow exponentially, like the fish of
+//! day 6 after thousands of days. Only what such counts need: adding, subtracting,
+//! comparing and printing.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Shr, Sub};
+
+/// An unsigned integer that grows as needed instead of overflowing.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    /// Digits in base 2^64, least significant first and without leading zero digits, so
+    /// every number has one representation and zero has none
+    limbs: Vec<u64>,
+}
+
+/// Largest power of ten that fits in a limb, for printing 19 decimal digits at a time
+const DECIMAL_CHUNK: u64 = 10_000_000_000_000_000_000;
+
+impl BigUint {
+    pub fn zero() -> BigUint {
+        BigUint::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    fn normalize(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+
+    /// Number of bits needed to write the number, 0 for zero.
+    pub fn bits(&self) -> u64 {
+        match self.limbs.last() {
+            Some(top) => self.limbs.len() as u64 * 64 - top.leading_zeros() as u64,
+            None => 0,
+        }
+    }
+
+    /// The number as `u128`, if it fits.
+    pub fn to_u128(&self) -> Option<u128> {
+        match self.limbs[..] {
+            [] => Some(0),
+            [low] => Some(low as u128),
+            [low, high] => Some((high as u128) << 64 | low as u128),
+            _ => None,
+        }
+    }
+
+    /// `self - other`, or `None` if `other` is larger.
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        if self < other {
+            return None;
+        }
+        let mut limbs = self.limbs.clone();
+        let mut borrow = false;
+        for (idx, limb) in limbs.iter_mut().enumerate() {
+            if idx >= other.limbs.len() && !borrow {
+                break;
+            }
+            let (diff, borrow1) = limb.overflowing_sub(other.limbs.get(idx).copied().unwrap_or(0));
+            let (diff, borrow2) = diff.overflowing_sub(borrow as u64);
+            *limb = diff;
+            borrow = borrow1 || borrow2;
+        }
+        let mut diff = BigUint { limbs };
+        diff.normalize();
+        Some(diff)
+    }
+
+    /// Divides by `divisor` in place and returns the remainder.
+    fn div_rem_small(&mut self, divisor: u64) -> u64 {
+        let mut rem: u128 = 0;
+        for limb in self.limbs.iter_mut().rev() {
+            let value = rem << 64 | *limb as u128;
+            *limb = (value / divisor as u128) as u64;
+            rem = value % divisor as u128;
+        }
+        self.normalize();
+        rem as u64
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(num: u64) -> BigUint {
+        let mut big = BigUint { limbs: vec![num] };
+        big.normalize();
+        big
+    }
+}
+
+impl From<usize> for BigUint {
+    fn from(num: usize) -> BigUint {
+        BigUint::from(num as u64)
+    }
+}
+
+impl AddAssign<&BigUint> for BigUint {
+    fn add_assign(&mut self, other: &BigUint) {
+        if self.limbs.len() < other.limbs.len() {
+            self.limbs.resize(other.limbs.len(), 0);
+        }
+        let mut carry = false;
+        for (idx, limb) in self.limbs.iter_mut().enumerate() {
+            if idx >= other.limbs.len() && !carry {
+                break;
+            }
+            let (sum, carry1) = limb.overflowing_add(other.limbs.get(idx).copied().unwrap_or(0));
+            let (sum, carry2) = sum.overflowing_add(carry as u64);
+            *limb = sum;
+            carry = carry1 || carry2;
+        }
+        if carry {
+            self.limbs.push(1);
+        }
+    }
+}
+
+impl Add<&BigUint> for BigUint {
+    type Output = BigUint;
+
+    fn add(mut self, other: &BigUint) -> BigUint {
+        self += other;
+        self
+    }
+}
+
+impl Sub<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    /// Panics if `other` is larger, like subtracting the built-in unsigned integers.
+    fn sub(self, other: &BigUint) -> BigUint {
+        self.checked_sub(other).expect("attempt to subtract with overflow")
+    }
+}
+
+impl Shr<u64> for &BigUint {
+    type Output = BigUint;
+
+    fn shr(self, bits: u64) -> BigUint {
+        let skip = (bits / 64) as usize;
+        let bits = (bits % 64) as u32;
+        let kept = self.limbs.get(skip..).unwrap_or(&[]);
+        let limbs = kept
+            .iter()
+            .enumerate()
+            .map(|(idx, limb)| match (bits, kept.get(idx + 1)) {
+                (0, _) | (_, None) => limb >> bits,
+                (_, Some(next)) => limb >> bits | next << (64 - bits),
+            })
+            .collect();
+        let mut shifted = BigUint { limbs };
+        shifted.normalize();
+        shifted
+    }
+}
+
+impl<'a> Sum<&'a BigUint> for BigUint {
+    fn sum<I: Iterator<Item = &'a BigUint>>(iter: I) -> BigUint {
+        iter.fold(BigUint::zero(), |sum, num| sum + num)
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        self.limbs.len().cmp(&other.limbs.len()).then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Prints the decimal digits, like the built-in integers.
+impl fmt::Debug for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rest = self.clone();
+        let mut chunks = Vec::new();
+        while !rest.is_zero() {
+            chunks.push(rest.div_rem_small(DECIMAL_CHUNK));
+        }
+        let mut digits = chunks.pop().unwrap_or(0).to_string();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:019}", chunk));
+        }
+        f.pad_integral(true, "", &digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BigUint;
+
+    /// 2^exp, by doubling
+    fn power_of_two(exp: u32) -> BigUint {
+        let mut num = BigUint::from(1u64);
+        for _ in 0..exp {
+            num = num.clone() + &num;
+        }
+        num
+    }
+
+    #[test]
+    fn add_and_sub() {
+        let max = BigUint::from(u64::MAX);
+        let sum = max.clone() + &BigUint::from(1u64);
+        assert_eq!(sum.to_u128(), Some(1 << 64));
+        assert_eq!(&sum - &max, BigUint::from(1u64));
+        assert_eq!(max.checked_sub(&sum), None);
+        assert_eq!(&sum - &sum, BigUint::zero());
+        assert!((&sum - &sum).is_zero());
+
+        let nums = [BigUint::from(u64::MAX), BigUint::from(u64::MAX), BigUint::from(2u64)];
+        assert_eq!(nums.iter().sum::<BigUint>().to_u128(), Some(u64::MAX as u128 * 2 + 2));
+    }
+
+    #[test]
+    fn compare() {
+        assert!(power_of_two(64) > BigUint::from(u64::MAX));
+        assert!(power_of_two(200) > power_of_two(199) + &power_of_two(198));
+        assert!(BigUint::zero() < BigUint::from(1u64));
+        assert_eq!(BigUint::from(0u64), BigUint::zero());
+    }
+
+    #[test]
+    fn bits_and_shift() {
+        assert_eq!(BigUint::zero().bits(), 0);
+        assert_eq!(power_of_two(130).bits(), 131);
+        assert_eq!(&power_of_two(130) >> 70, power_of_two(60));
+        assert_eq!(&power_of_two(130) >> 128, BigUint::from(4u64));
+        assert_eq!(&power_of_two(130) >> 131, BigUint::zero());
+        assert_eq!(power_of_two(130).to_u128(), None);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(BigUint::zero().to_string(), "0");
+        assert_eq!(BigUint::from(26984457539u64).to_string(), "26984457539");
+        assert_eq!(power_of_two(64).to_string(), "18446744073709551616");
+        assert_eq!(power_of_two(128).to_string(), "340282366920938463463374607431768211456");
+        assert_eq!(format!("{:>6}", BigUint::from(42u64)), "    42");
+    }
+}
@@ -0,0 +1,96 @@
+use std::error::Error;
+use std::fmt;
+
+/// An error in the puzzle input, pointing at the offending text like a compiler would.
+///
+/// Line and column are 1-based. Displays as `12:7: expected ',' after X coordinate, found ';'`,
+/// to be prefixed with the name of the input by whoever reports it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AocError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub expected: String,
+}
+
+impl AocError {
+    pub fn new(line: usize, column: usize, text: &str, expected: &str) -> AocError {
+        AocError {
+            line,
+            column,
+            text: text.to_string(),
+            expected: expected.to_string(),
+        }
+    }
+
+    /// Error about `part`, which should be a slice of `line` so the column can be derived
+    /// from its position. Anything else is reported at the start of the line.
+    pub fn at(line_nr: usize, line: &str, part: &str, expected: &str) -> AocError {
+        let start = line.as_ptr() as usize;
+        let ofs = (part.as_ptr() as usize).wrapping_sub(start);
+        let column = if ofs <= line.len() && line.is_char_boundary(ofs) {
+            line[..ofs].chars().count() + 1
+        } else {
+            1
+        };
+        AocError::new(line_nr, column, part, expected)
+    }
+
+    /// Error about the first character of `rest`, a slice of `line`.
+    pub fn at_first_char(line_nr: usize, line: &str, rest: &str, expected: &str) -> AocError {
+        let len = rest.chars().next().map(|c| c.len_utf8()).unwrap_or(0);
+        AocError::at(line_nr, line, &rest[..len], expected)
+    }
+
+    /// Error about something missing at the end of `line`.
+    pub fn at_end(line_nr: usize, line: &str, expected: &str) -> AocError {
+        AocError::new(line_nr, line.chars().count() + 1, "", expected)
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: expected {}", self.line, self.column, self.expected)?;
+        if !self.text.is_empty() {
+            write!(f, ", found '{}'", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for AocError {}
+
+#[cfg(test)]
+mod tests {
+    use super::AocError;
+
+    #[test]
+    fn column_from_slice() {
+        let line = "12;34";
+        let err = AocError::at(12, line, &line[2..3], "',' after X coordinate");
+        assert_eq!(err.column, 3);
+        assert_eq!(err.to_string(), "12:3: expected ',' after X coordinate, found ';'");
+    }
+
+    #[test]
+    fn column_in_chars() {
+        let line = "é,ü;";
+        let err = AocError::at_first_char(1, line, &line[5..], "','");
+        assert_eq!(err.to_string(), "1:4: expected ',', found ';'");
+        let err = AocError::at_first_char(1, line, &line[3..], "a digit");
+        assert_eq!(err.text, "ü");
+    }
+
+    #[test]
+    fn column_at_end() {
+        let err = AocError::at_end(2, "forward", "a number");
+        assert_eq!(err.to_string(), "2:8: expected a number");
+    }
+
+    #[test]
+    fn unrelated_slice() {
+        let other = String::from("xyz");
+        let err = AocError::at(1, "abc", &other, "a number");
+        assert_eq!(err.column, 1);
+    }
+}
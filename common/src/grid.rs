@@ -0,0 +1,239 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A rectangular 2D grid with flat, row-major storage. Positions are `(x, y)` with
+/// `(0, 0)` in the top left corner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+const OFFSETS4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const OFFSETS8: [(isize, isize); 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid { width, height, cells: vec![value; width * height] }
+    }
+
+    /// Builds a grid from its cells in row-major order.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(cells.len(), width * height, "Cell count does not match the grid size");
+        Grid { width, height, cells }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[pos.1 * self.width + pos.0])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.1 * self.width + pos.0])
+        } else {
+            None
+        }
+    }
+
+    /// All cells in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |idx| (idx % width, idx / width))
+    }
+
+    /// All cells with their position, in row-major order.
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks() panics on a chunk size of 0, an empty row never has cells anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {} out of range", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    fn neighbors(&self, (x, y): (usize, usize), offsets: &'static [(isize, isize)]) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let nx = x.checked_add_signed(*dx)?;
+            let ny = y.checked_add_signed(*dy)?;
+            if nx < width && ny < height {
+                Some((nx, ny))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Positions of the horizontally and vertically adjacent cells that are inside the grid.
+    pub fn neighbors4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbors(pos, &OFFSETS4)
+    }
+
+    /// Like `neighbors4`, including the diagonally adjacent cells.
+    pub fn neighbors8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbors(pos, &OFFSETS8)
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+
+    /// Mirrors the grid over its diagonal, so rows become columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).cloned().collect::<Vec<T>>())
+            .collect();
+        Grid { width: self.height, height: self.width, cells }
+    }
+
+    /// Grows or shrinks the grid to `width` x `height`, keeping the cells that stay
+    /// inside and filling new ones with `value`.
+    pub fn resize(&mut self, width: usize, height: usize, value: T)
+    where
+        T: Clone,
+    {
+        if width == self.width && height == self.height {
+            return;
+        }
+        let mut cells: Vec<T> = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(self.get((x, y)).cloned().unwrap_or_else(|| value.clone()));
+            }
+        }
+        *self = Grid { width, height, cells };
+    }
+
+    /// Keeps only the top left `width` x `height` part of the grid.
+    pub fn crop(&mut self, width: usize, height: usize) {
+        let width = width.min(self.width);
+        let height = height.min(self.height);
+        let old_width = self.width;
+        let mut idx = 0;
+        self.cells.retain(|_| {
+            let keep = idx % old_width < width && idx / old_width < height;
+            idx += 1;
+            keep
+        });
+        self.width = width;
+        self.height = height;
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width && y < self.height, "Position ({}, {}) out of range", x, y);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width && y < self.height, "Position ({}, {}) out of range", x, y);
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+/// Writes each row on its own line, with the cells next to each other.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = Grid::new(3, 3, 0);
+        let mut corner: Vec<(usize, usize)> = grid.neighbors4((0, 0)).collect();
+        corner.sort();
+        assert_eq!(corner, vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8((2, 2)).count(), 3);
+        assert_eq!(grid.neighbors8((2, 0)).count(), 3);
+    }
+
+    #[test]
+    fn views() {
+        let grid = crate::parse::char_grid(&lines("ab\ncd\nef"), "a letter", Some).unwrap();
+        assert_eq!(grid.row(1), &['c', 'd']);
+        assert_eq!(grid.column(1).collect::<String>(), "bdf");
+        assert_eq!(grid.transpose().to_string(), "ace\nbdf\n");
+        assert_eq!(grid.cells().find(|(_, c)| **c == 'd').map(|(pos, _)| pos), Some((1, 1)));
+
+        let mut grid = grid;
+        grid.crop(1, 2);
+        assert_eq!(grid.to_string(), "a\nc\n");
+        grid.resize(2, 3, '.');
+        assert_eq!(grid.to_string(), "a.\nc.\n..\n");
+    }
+}
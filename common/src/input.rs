@@ -0,0 +1,164 @@
+use std::fs::File;
+use std::io::{self, BufRead};
+
+/// Where the puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// A file on disk, `--input <path>`
+    File(String),
+    /// Standard input, `-` or `--input -`
+    Stdin,
+    /// The example from the puzzle description that is embedded in the day crate, `--example`
+    Example,
+}
+
+impl InputSource {
+    /// Name to prefix parse errors with, so they read like `day05/input:12:7: ...`.
+    pub fn name(&self) -> &str {
+        match self {
+            InputSource::File(path) => path,
+            InputSource::Stdin => "<stdin>",
+            InputSource::Example => "<example>",
+        }
+    }
+
+    /// Reads all lines from the source; `example` is used for `InputSource::Example`.
+    pub fn read_lines(&self, example: &str) -> Result<Vec<String>, String> {
+        let lines: io::Result<Vec<String>> = match self {
+            InputSource::File(path) => {
+                let file = File::open(path).map_err(|e| format!("unreadable input file {}: {}", path, e))?;
+                io::BufReader::new(file).lines().collect()
+            }
+            InputSource::Stdin => io::stdin().lock().lines().collect(),
+            InputSource::Example => return Ok(example.lines().map(|x| x.to_string()).collect()),
+        };
+        lines.map_err(|e| format!("could not read {}: {}", self.name(), e))
+    }
+
+    /// Opens the source for reading one line at a time, see `LineReader`.
+    pub fn open(&self, example: &'static str) -> Result<LineReader, String> {
+        match self {
+            InputSource::File(path) => {
+                let file = File::open(path).map_err(|e| format!("unreadable input file {}: {}", path, e))?;
+                Ok(LineReader::new(io::BufReader::new(file)))
+            }
+            InputSource::Stdin => Ok(LineReader::new(io::stdin().lock())),
+            InputSource::Example => Ok(LineReader::new(io::Cursor::new(example))),
+        }
+    }
+}
+
+/// Splits raw bytes into lines like `read_lines` does, replacing invalid UTF-8; for
+/// feeding arbitrary data to the parsers when fuzzing.
+pub fn lossy_lines(data: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(data).lines().map(|x| x.to_string()).collect()
+}
+
+/// Reads input one line at a time into a reused buffer, so memory use does not grow
+/// with the size of the input.
+///
+/// A read error ends the lines as if the input ended there; `take_error` tells the two apart.
+pub struct LineReader {
+    reader: Box<dyn BufRead>,
+    buf: String,
+    line_nr: usize,
+    error: Option<io::Error>,
+}
+
+impl LineReader {
+    pub fn new<R: BufRead + 'static>(reader: R) -> LineReader {
+        LineReader { reader: Box::new(reader), buf: String::new(), line_nr: 0, error: None }
+    }
+
+    /// The next line and its 1-based line number, without the line ending.
+    pub fn next_line(&mut self) -> Option<(usize, &str)> {
+        if self.error.is_some() {
+            return None;
+        }
+
+        self.buf.clear();
+        match self.reader.read_line(&mut self.buf) {
+            Ok(0) => None,
+            Ok(_) => {
+                self.line_nr += 1;
+                let line = self.buf.strip_suffix('\n').unwrap_or(&self.buf);
+                Some((self.line_nr, line.strip_suffix('\r').unwrap_or(line)))
+            }
+            Err(e) => {
+                self.error = Some(e);
+                None
+            }
+        }
+    }
+
+    /// The read error that ended the lines early, if any.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+}
+
+/// Recognizes the input selection options `--input <path>`, `-` and `--example`.
+///
+/// Returns `Ok(None)` if `arg` is not one of them; the path of `--input` is taken from `rest`.
+pub fn parse_input_arg<'a, I>(arg: &str, rest: &mut I) -> Result<Option<InputSource>, String>
+where
+    I: Iterator<Item = &'a String>,
+{
+    match arg {
+        "--input" | "-i" => match rest.next().map(|x| x.as_str()) {
+            Some("-") => Ok(Some(InputSource::Stdin)),
+            Some(path) => Ok(Some(InputSource::File(path.to_string()))),
+            None => Err(String::from("--input needs a path")),
+        },
+        "-" => Ok(Some(InputSource::Stdin)),
+        "--example" => Ok(Some(InputSource::Example)),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InputSource;
+
+    fn parse(line: &str) -> Result<Option<InputSource>, String> {
+        let args: Vec<String> = line.split_whitespace().map(|x| x.to_string()).collect();
+        let mut rest = args[1..].iter();
+        super::parse_input_arg(&args[0], &mut rest)
+    }
+
+    #[test]
+    fn parse_input_arg() {
+        assert_eq!(parse("--input day05/input"), Ok(Some(InputSource::File(String::from("day05/input")))));
+        assert_eq!(parse("--input -"), Ok(Some(InputSource::Stdin)));
+        assert_eq!(parse("-"), Ok(Some(InputSource::Stdin)));
+        assert_eq!(parse("--example"), Ok(Some(InputSource::Example)));
+        assert_eq!(parse("--part 2"), Ok(None));
+        assert!(parse("--input").is_err());
+    }
+
+    #[test]
+    fn read_lines() {
+        let lines = InputSource::Example.read_lines("1\n2\n").unwrap();
+        assert_eq!(lines, vec![String::from("1"), String::from("2")]);
+
+        let err = InputSource::File(String::from("does/not/exist")).read_lines("").unwrap_err();
+        assert!(err.starts_with("unreadable input file does/not/exist: "));
+
+        assert_eq!(super::lossy_lines(b"1\r\n\xff2"), vec![String::from("1"), String::from("\u{fffd}2")]);
+    }
+
+    #[test]
+    fn line_reader() {
+        let mut reader = super::LineReader::new(std::io::Cursor::new("1\r\n\n3"));
+        assert_eq!(reader.next_line(), Some((1, "1")));
+        assert_eq!(reader.next_line(), Some((2, "")));
+        assert_eq!(reader.next_line(), Some((3, "3")));
+        assert_eq!(reader.next_line(), None);
+        assert!(reader.take_error().is_none());
+
+        let mut reader = InputSource::Example.open("a\nb\n").unwrap();
+        assert_eq!(reader.next_line(), Some((1, "a")));
+        assert_eq!(reader.next_line(), Some((2, "b")));
+        assert_eq!(reader.next_line(), None);
+    }
+}
@@ -0,0 +1,21 @@
+pub mod answers;
+pub mod bigint;
+pub mod error;
+pub mod grid;
+pub mod input;
+pub mod ocr;
+pub mod params;
+pub mod parse;
+pub mod prop;
+pub mod render;
+pub mod repl;
+pub mod rng;
+pub mod search;
+pub mod solution;
+pub mod visual;
+
+pub use answers::Answers;
+pub use error::AocError;
+pub use grid::Grid;
+pub use input::{parse_input_arg, InputSource, LineReader};
+pub use solution::{run_day, Answer, Part, Puzzle, Solution};
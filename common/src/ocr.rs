@@ -0,0 +1,144 @@
+//! Reads the capital letters that some puzzles draw with dots, in the font of 4 by 6
+//! dots that Advent of Code uses for them. Letters sit next to each other with one
+//! empty column in between, so letter `n` starts at column `5 * n`.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::grid::Grid;
+
+pub const GLYPH_WIDTH: usize = 4;
+pub const GLYPH_HEIGHT: usize = 6;
+
+/// Distance from the start of one letter to the next, including the empty column.
+const PITCH: usize = GLYPH_WIDTH + 1;
+
+/// The letters that have shown up in puzzle answers so far.
+static FONT: &[(char, [&str; GLYPH_HEIGHT])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    /// The image is not as high as a letter; holds its height.
+    Height(usize),
+    /// The letter at `index`, counted from 0 on the left, is not in the font. The
+    /// bitmap has the dots of the letter, plus the column after it if that is not empty.
+    UnknownGlyph { index: usize, bitmap: Grid<bool> },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::Height(height) => {
+                write!(f, "letters are {} dots high, the image is {}", GLYPH_HEIGHT, height)
+            }
+            OcrError::UnknownGlyph { index, bitmap } => {
+                writeln!(f, "unknown letter {}:", index + 1)?;
+                write!(f, "{}", bitmap.map(|dot| if *dot { '#' } else { '.' }))
+            }
+        }
+    }
+}
+
+impl Error for OcrError {}
+
+/// The letter drawn by the dots in `glyph`, if it is in the font.
+fn recognize_glyph(glyph: &Grid<bool>) -> Option<char> {
+    FONT.iter()
+        .find(|(_, rows)| {
+            rows.iter().zip(glyph.rows()).all(|(expected, row)| expected.chars().zip(row).all(|(c, dot)| (c == '#') == *dot))
+        })
+        .map(|(letter, _)| *letter)
+}
+
+/// Reads the letters in `dots`. Empty space after the last letter is ignored.
+pub fn recognize(dots: &Grid<bool>) -> Result<String, OcrError> {
+    if dots.height() != GLYPH_HEIGHT {
+        return Err(OcrError::Height(dots.height()));
+    }
+
+    let used_width = (0..dots.width()).rev().find(|&x| dots.column(x).any(|dot| *dot)).map_or(0, |x| x + 1);
+    let mut text = String::new();
+    for index in 0..used_width.div_ceil(PITCH) {
+        let left = index * PITCH;
+        let spacer_empty = left + GLYPH_WIDTH >= dots.width() || !dots.column(left + GLYPH_WIDTH).any(|dot| *dot);
+        let width = if spacer_empty { GLYPH_WIDTH } else { PITCH };
+        let mut glyph = Grid::new(width, GLYPH_HEIGHT, false);
+        for y in 0..GLYPH_HEIGHT {
+            for x in 0..width {
+                glyph[(x, y)] = dots.get((left + x, y)).copied().unwrap_or(false);
+            }
+        }
+
+        match recognize_glyph(&glyph) {
+            Some(letter) if spacer_empty => text.push(letter),
+            _ => return Err(OcrError::UnknownGlyph { index, bitmap: glyph }),
+        }
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OcrError;
+    use crate::grid::Grid;
+
+    fn image(rows: &[&str]) -> Grid<bool> {
+        let cells = rows.iter().flat_map(|row| row.chars().map(|c| c == '#')).collect();
+        Grid::from_vec(rows[0].len(), rows.len(), cells)
+    }
+
+    #[test]
+    fn recognize() {
+        let dots = image(&[
+            "#..#.####.###..",
+            "#..#....#.#..#.",
+            "####...#..#..#.",
+            "#..#..#...###..",
+            "#..#.#....#.#..",
+            "#..#.####.#..#.",
+        ]);
+        assert_eq!(super::recognize(&dots), Ok(String::from("HZR")));
+
+        // Every letter of the font reads back as itself
+        for (letter, rows) in super::FONT {
+            assert_eq!(super::recognize(&image(rows)), Ok(letter.to_string()));
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let dots = image(&["####.#..#", "#....#..#", "###..####", "#....#..#", "#....#..#", "####.#.##"]);
+        let err = super::recognize(&dots).unwrap_err();
+        assert_eq!(err.to_string(), "unknown letter 2:\n#..#\n#..#\n####\n#..#\n#..#\n#.##\n");
+        match err {
+            OcrError::UnknownGlyph { index, bitmap } => assert_eq!((index, bitmap.width()), (1, 4)),
+            _ => panic!("Expected an unknown glyph"),
+        }
+
+        // A dot between two letters belongs to neither
+        let dots = image(&["####.", "#...#", "###..", "#....", "#....", "####."]);
+        let err = super::recognize(&dots).unwrap_err();
+        assert_eq!(err.to_string(), "unknown letter 1:\n####.\n#...#\n###..\n#....\n#....\n####.\n");
+
+        let dots = image(&["#", "#", "#"]);
+        assert_eq!(super::recognize(&dots), Err(OcrError::Height(3)));
+    }
+}
@@ -0,0 +1,91 @@
+//! Puzzle parameters, like the number of days to simulate, that can be changed with
+//! `--param name=value`. Their defaults are the values from the puzzle description.
+
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// A parameter a puzzle accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    /// The value from the puzzle description, as it would be written on the command line
+    pub default: &'static str,
+    pub help: &'static str,
+}
+
+/// Splits a `name=value` argument.
+pub fn assignment(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_string(), value.to_string())),
+        _ => Err(format!("invalid parameter '{}', expected name=value", arg)),
+    }
+}
+
+/// `value` of parameter `name` as a number within `range`.
+pub fn number<T>(name: &str, value: &str, range: RangeInclusive<T>) -> Result<T, String>
+where
+    T: FromStr + PartialOrd + Display,
+{
+    match value.parse::<T>() {
+        Ok(num) if range.contains(&num) => Ok(num),
+        _ => Err(format!(
+            "invalid value '{}' for {}, expected a number from {} to {}",
+            value,
+            name,
+            range.start(),
+            range.end()
+        )),
+    }
+}
+
+/// `value` of parameter `name` as a number within `range` for each part: `one,two`, or
+/// a single number for both.
+pub fn per_part<T>(name: &str, value: &str, range: RangeInclusive<T>) -> Result<(T, T), String>
+where
+    T: FromStr + PartialOrd + Display + Copy,
+{
+    match value.split_once(',') {
+        Some((one, two)) => Ok((number(name, one, range.clone())?, number(name, two, range)?)),
+        None => {
+            let num = number(name, value, range)?;
+            Ok((num, num))
+        }
+    }
+}
+
+/// Lists `params` with their defaults, one per line.
+pub fn describe(params: &[Param]) -> String {
+    params.iter().map(|p| format!("  {}={}: {}\n", p.name, p.default, p.help)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn assignment() {
+        assert_eq!(super::assignment("days=1000"), Ok((String::from("days"), String::from("1000"))));
+        assert_eq!(super::assignment("target=x=1..2"), Ok((String::from("target"), String::from("x=1..2"))));
+        assert!(super::assignment("days").is_err());
+        assert!(super::assignment("=5").is_err());
+    }
+
+    #[test]
+    fn number() {
+        assert_eq!(super::number::<usize>("days", "80", 0..=1000), Ok(80));
+        assert_eq!(
+            super::number::<usize>("days", "-1", 0..=1000),
+            Err(String::from("invalid value '-1' for days, expected a number from 0 to 1000"))
+        );
+        assert!(super::number::<usize>("days", "1001", 0..=1000).is_err());
+        assert!(super::number::<u32>("bits", "x", 1..=63).is_err());
+    }
+
+    #[test]
+    fn per_part() {
+        assert_eq!(super::per_part::<usize>("days", "80,256", 0..=1000), Ok((80, 256)));
+        assert_eq!(super::per_part::<usize>("days", "18", 0..=1000), Ok((18, 18)));
+        assert!(super::per_part::<usize>("days", "80,", 0..=1000).is_err());
+        assert!(super::per_part::<usize>("days", "80,2000", 0..=1000).is_err());
+        assert!(super::per_part::<usize>("days", "1,2,3", 0..=1000).is_err());
+    }
+}
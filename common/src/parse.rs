@@ -0,0 +1,265 @@
+//! Parsers for the input shapes that keep coming back, all reporting malformed input
+//! as an `AocError` pointing at the offending text.
+//!
+//! Line numbers are 1-based, and the `text` passed to the line level helpers should be
+//! a slice of `line` so errors get the right column.
+
+use std::str::FromStr;
+
+use crate::error::AocError;
+use crate::grid::Grid;
+
+/// Parses `text` as a number, or whatever else implements `FromStr`.
+pub fn number<T: FromStr>(line_nr: usize, line: &str, text: &str, what: &str) -> Result<T, AocError> {
+    text.parse::<T>().map_err(|_| AocError::at(line_nr, line, text, what))
+}
+
+/// Parses the `sep`-separated values in `text`, like `3,4,3,1,2`.
+pub fn separated<T: FromStr>(line_nr: usize, line: &str, text: &str, sep: char, what: &str) -> Result<Vec<T>, AocError> {
+    text.split(sep).map(|x| number(line_nr, line, x, what)).collect()
+}
+
+/// Parses a line of comma-separated values.
+pub fn comma_separated<T: FromStr>(line_nr: usize, line: &str, what: &str) -> Result<Vec<T>, AocError> {
+    separated(line_nr, line, line, ',', what)
+}
+
+/// The only line of an input that consists of a single line.
+pub fn single_line<'a>(lines: &'a [String], what: &str) -> Result<&'a str, AocError> {
+    match lines {
+        [] => Err(AocError::new(1, 1, "", what)),
+        [line] => Ok(line),
+        [_, extra, ..] => Err(AocError::at(2, extra, extra, "end of input")),
+    }
+}
+
+/// Splits `text` in the parts before and after the first `sep`.
+///
+/// When `sep` is missing the error points at where its first character is, or at the
+/// end of `text` if that is missing too.
+pub fn split_pair<'a>(line_nr: usize, line: &str, text: &'a str, sep: &str, what: &str) -> Result<(&'a str, &'a str), AocError> {
+    text.split_once(sep).ok_or_else(|| {
+        let first = sep.chars().next().unwrap_or(' ');
+        let ofs = text.find(first).unwrap_or(text.len());
+        AocError::at(line_nr, line, &text[ofs..], what)
+    })
+}
+
+/// Splits a rule like `CH -> B` or `0,9 -> 5,9` in its two sides.
+pub fn arrow<'a>(line_nr: usize, line: &'a str, what: &str) -> Result<(&'a str, &'a str), AocError> {
+    split_pair(line_nr, line, line, " -> ", what)
+}
+
+/// Splits an edge like `start-A` in its two ends.
+pub fn edge<'a>(line_nr: usize, line: &'a str, what: &str) -> Result<(&'a str, &'a str), AocError> {
+    split_pair(line_nr, line, line, "-", what)
+}
+
+/// Parses an `x,y` coordinate pair.
+pub fn coordinate(line_nr: usize, line: &str, text: &str) -> Result<(usize, usize), AocError> {
+    let (x, y) = text.split_once(',').ok_or_else(|| {
+        let ofs = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+        AocError::at_first_char(line_nr, line, &text[ofs..], "',' after X coordinate")
+    })?;
+    let x = number(line_nr, line, x, "X coordinate")?;
+    let y = number(line_nr, line, y, "Y coordinate")?;
+
+    Ok((x, y))
+}
+
+/// A run of lines between blank lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// Line number of the first line
+    pub first_line: usize,
+    pub lines: &'a [String],
+}
+
+impl<'a> Section<'a> {
+    /// The lines with their line numbers.
+    pub fn numbered(&self) -> impl Iterator<Item = (usize, &'a String)> {
+        let first_line = self.first_line;
+        self.lines.iter().enumerate().map(move |(idx, line)| (first_line + idx, line))
+    }
+
+    /// Line number just after the section, for reporting what is missing at its end.
+    pub fn end_line(&self) -> usize {
+        self.first_line + self.lines.len()
+    }
+}
+
+/// The sections of an input separated by blank lines, taken one at a time so they are
+/// checked in input order. A blank line at the very end does not start a new section.
+#[derive(Debug, Clone)]
+pub struct Sections<'a> {
+    lines: &'a [String],
+    /// Index of the first line of the next section, `None` when all are taken
+    start: Option<usize>,
+}
+
+pub fn sections(lines: &[String]) -> Sections<'_> {
+    Sections { lines, start: Some(0) }
+}
+
+impl<'a> Sections<'a> {
+    /// The next section, which must have at least one line.
+    pub fn expect(&mut self, what: &str) -> Result<Section<'a>, AocError> {
+        match self.next() {
+            Some(section) if !section.lines.is_empty() => Ok(section),
+            Some(section) => Err(AocError::new(section.first_line, 1, "", what)),
+            None => Err(AocError::new(self.lines.len() + 1, 1, "", what)),
+        }
+    }
+
+    /// Like `expect`, for the section that has to be the last one.
+    pub fn expect_last(&mut self, what: &str) -> Result<Section<'a>, AocError> {
+        let section = self.expect(what)?;
+        if self.start.is_some() {
+            return Err(AocError::new(section.end_line(), 1, "", "end of input"));
+        }
+        Ok(section)
+    }
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = Section<'a>;
+
+    fn next(&mut self) -> Option<Section<'a>> {
+        let start = self.start?;
+        let rest = &self.lines[start..];
+        match rest.iter().position(|line| line.is_empty()) {
+            Some(len) => {
+                let next = start + len + 1;
+                self.start = if next < self.lines.len() { Some(next) } else { None };
+                Some(Section { first_line: start + 1, lines: &rest[..len] })
+            }
+            None => {
+                self.start = None;
+                Some(Section { first_line: start + 1, lines: rest })
+            }
+        }
+    }
+}
+
+/// Parses lines with one character per cell; `cell` returns `None` for characters that
+/// are not allowed, which are reported as `expected <what>`.
+pub fn char_grid<T, F>(lines: &[String], what: &str, mut cell: F) -> Result<Grid<T>, AocError>
+where
+    F: FnMut(char) -> Option<T>,
+{
+    let first = lines.first().ok_or_else(|| AocError::new(1, 1, "", what))?;
+    let width = first.chars().count();
+    if width == 0 {
+        return Err(AocError::new(1, 1, "", what));
+    }
+
+    let mut cells: Vec<T> = Vec::with_capacity(width * lines.len());
+    for (idx, line) in lines.iter().enumerate() {
+        let mut count = 0;
+        for (pos, c) in line.char_indices() {
+            if count == width {
+                return Err(AocError::at(idx + 1, line, &line[pos..], "end of line"));
+            }
+            cells.push(cell(c).ok_or_else(|| AocError::at_first_char(idx + 1, line, &line[pos..], what))?);
+            count += 1;
+        }
+        if count < width {
+            return Err(AocError::at_end(idx + 1, line, what));
+        }
+    }
+
+    Ok(Grid::from_vec(width, lines.len(), cells))
+}
+
+/// Parses lines of decimal digits, one per cell.
+pub fn digit_grid<T: From<u8>>(lines: &[String], what: &str) -> Result<Grid<T>, AocError> {
+    char_grid(lines, what, |c| c.to_digit(10).map(|d| T::from(d as u8)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::Grid;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(super::comma_separated::<u32>(1, "3,4,3", "a number"), Ok(vec![3, 4, 3]));
+        let err = super::comma_separated::<u32>(1, "3,x,3", "a number").unwrap_err();
+        assert_eq!(err.to_string(), "1:3: expected a number, found 'x'");
+        let err = super::comma_separated::<u32>(1, "3,,3", "a number").unwrap_err();
+        assert_eq!(err.to_string(), "1:3: expected a number");
+
+        assert_eq!(super::single_line(&lines("1,2"), "numbers"), Ok("1,2"));
+        assert_eq!(super::single_line(&[], "numbers").unwrap_err().to_string(), "1:1: expected numbers");
+        let err = super::single_line(&lines("1,2\n3"), "numbers").unwrap_err();
+        assert_eq!(err.to_string(), "2:1: expected end of input, found '3'");
+    }
+
+    #[test]
+    fn pairs() {
+        assert_eq!(super::arrow(1, "CH -> B", "a rule"), Ok(("CH", "B")));
+        let err = super::arrow(4, "CH => B", "' -> '").unwrap_err();
+        assert_eq!(err.to_string(), "4:3: expected ' -> ', found ' => B'");
+        let err = super::arrow(4, "CH", "' -> '").unwrap_err();
+        assert_eq!(err.to_string(), "4:3: expected ' -> '");
+
+        assert_eq!(super::edge(1, "start-A", "an edge"), Ok(("start", "A")));
+        let err = super::edge(1, "start", "'-'").unwrap_err();
+        assert_eq!(err.to_string(), "1:6: expected '-'");
+
+        assert_eq!(super::coordinate(1, "6,10", "6,10"), Ok((6, 10)));
+        let err = super::coordinate(2, "0,9 -> 5;9", &"0,9 -> 5;9"[7..]).unwrap_err();
+        assert_eq!(err.to_string(), "2:9: expected ',' after X coordinate, found ';'");
+        let err = super::coordinate(2, "1010", "1010").unwrap_err();
+        assert_eq!(err.to_string(), "2:5: expected ',' after X coordinate");
+    }
+
+    #[test]
+    fn sections() {
+        let input = lines("a\nb\n\nc\n\n\nd\n");
+        let sections: Vec<super::Section> = super::sections(&input).collect();
+        assert_eq!(sections.len(), 4);
+        assert_eq!(sections[0].numbered().collect::<Vec<_>>(), vec![(1, &input[0]), (2, &input[1])]);
+        assert_eq!(sections[1].numbered().collect::<Vec<_>>(), vec![(4, &input[3])]);
+        assert!(sections[2].lines.is_empty());
+        assert_eq!(sections[3].first_line, 7);
+        assert_eq!(super::sections(&[]).count(), 1);
+
+        let input = lines("a\nb\n\nc");
+        let mut sections = super::sections(&input);
+        assert_eq!(sections.expect("dots").unwrap().lines.len(), 2);
+        assert_eq!(sections.expect_last("folds").unwrap().first_line, 4);
+
+        let err = super::sections(&lines("\nc")).expect("dots").unwrap_err();
+        assert_eq!(err.to_string(), "1:1: expected dots");
+        let input = lines("a\nb");
+        let mut sections = super::sections(&input);
+        sections.expect("dots").unwrap();
+        assert_eq!(sections.expect("folds").unwrap_err().to_string(), "3:1: expected folds");
+        let input = lines("a\n\nc\n\nd");
+        let mut sections = super::sections(&input);
+        sections.expect("dots").unwrap();
+        assert_eq!(sections.expect_last("folds").unwrap_err().to_string(), "4:1: expected end of input");
+    }
+
+    #[test]
+    fn digit_grid() {
+        let grid: Grid<u32> = super::digit_grid(&lines("123\n456"), "a digit").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(0, 0)], 1);
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), "123\n456\n");
+
+        let err = super::digit_grid::<u32>(&lines("123\n4x6"), "a digit").unwrap_err();
+        assert_eq!(err.to_string(), "2:2: expected a digit, found 'x'");
+        let err = super::digit_grid::<u32>(&lines("123\n45"), "a digit").unwrap_err();
+        assert_eq!(err.to_string(), "2:3: expected a digit");
+        let err = super::digit_grid::<u32>(&lines("123\n4567"), "a digit").unwrap_err();
+        assert_eq!(err.to_string(), "2:4: expected end of line, found '7'");
+        assert!(super::digit_grid::<u32>(&[], "a digit").is_err());
+    }
+}
@@ -0,0 +1,82 @@
+//! Property-based testing: check that something holds for many generated inputs.
+//!
+//! Each case is numbered, and its number determines both the seed of its random number
+//! generator and its size, which grows with the case number so the first failing case
+//! tends to be a small one. A failure panics with the case number and the input; set
+//! `AOC_PROP_CASE` to that number to replay exactly that case, or `AOC_PROP_CASES` to
+//! run more (or fewer) cases.
+
+use std::env;
+use std::fmt::Debug;
+
+use crate::rng::Rng;
+
+const SEED: u64 = 2021;
+
+/// Largest size passed to generators.
+pub const MAX_SIZE: usize = 100;
+
+fn env_number(name: &str) -> Option<u64> {
+    let value = env::var(name).ok()?;
+    match value.parse::<u64>() {
+        Ok(num) => Some(num),
+        Err(_) => panic!("{} must be a number, not '{}'", name, value),
+    }
+}
+
+/// Runs `property` on `cases` inputs made by `generate`, which gets a random number
+/// generator and a size from 0 to `MAX_SIZE`. Run at least `MAX_SIZE + 1` cases to
+/// reach the largest size.
+pub fn check<T, G, P>(cases: usize, mut generate: G, mut property: P)
+where
+    T: Debug,
+    G: FnMut(&mut Rng, usize) -> T,
+    P: FnMut(&T) -> Result<(), String>,
+{
+    let selected: Vec<u64> = match (env_number("AOC_PROP_CASE"), env_number("AOC_PROP_CASES")) {
+        (Some(case), _) => vec![case],
+        (None, Some(cases)) => (0..cases).collect(),
+        (None, None) => (0..cases as u64).collect(),
+    };
+
+    for case in selected {
+        let size = (case % (MAX_SIZE as u64 + 1)) as usize;
+        let mut rng = Rng::new(SEED.wrapping_add(case));
+        let input = generate(&mut rng, size);
+        if let Err(e) = property(&input) {
+            panic!("Property failed for AOC_PROP_CASE={} (size {}): {}\nInput: {:?}", case, size, e, input);
+        }
+    }
+}
+
+/// Fails the property with a message comparing `left` and `right` unless they are equal.
+pub fn equal<T: PartialEq + Debug>(left: T, right: T, what: &str) -> Result<(), String> {
+    if left == right {
+        Ok(())
+    } else {
+        Err(format!("{} differ: {:?} != {:?}", what, left, right))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn passes() {
+        let mut count = 0;
+        super::check(
+            50,
+            |rng, size| rng.range(0..size + 1),
+            |n| {
+                count += 1;
+                super::equal(*n <= super::MAX_SIZE, true, "bounds")
+            },
+        );
+        assert!(count >= 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Property failed for AOC_PROP_CASE=")]
+    fn fails_with_case() {
+        super::check(200, |rng, size| rng.range(0..size + 1), |n| super::equal(*n < 50, true, "small"));
+    }
+}
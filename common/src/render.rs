@@ -0,0 +1,340 @@
+//! Pictures of the grid puzzles: a sequence of frames of palette indices, written as an
+//! animated GIF or as one binary PPM per frame. Both formats are simple enough to
+//! write by hand, so no image crate is needed.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::grid::Grid;
+
+pub type Rgb = [u8; 3];
+
+/// Images are scaled up so their longest side is about this many pixels.
+const TARGET_SIZE: usize = 512;
+
+/// Largest number of pixels per cell.
+const MAX_SCALE: usize = 16;
+
+/// Frames of indices into a palette of at most 256 colours. Frames may differ in size;
+/// smaller ones are drawn in the top left corner on palette colour 0.
+#[derive(Debug, Clone)]
+pub struct Animation {
+    palette: Vec<Rgb>,
+    frames: Vec<Grid<u8>>,
+    /// Time between frames in hundredths of a second
+    delay: u16,
+}
+
+impl Animation {
+    pub fn new(palette: Vec<Rgb>) -> Animation {
+        assert!(!palette.is_empty() && palette.len() <= 256, "A palette has 1 to 256 colours");
+        Animation { palette, frames: Vec::new(), delay: 10 }
+    }
+
+    /// Sets the time between frames, in hundredths of a second.
+    pub fn with_delay(mut self, delay: u16) -> Animation {
+        self.delay = delay;
+        self
+    }
+
+    pub fn push_frame(&mut self, frame: Grid<u8>) {
+        self.frames.push(frame);
+    }
+
+    pub fn frames(&self) -> &[Grid<u8>] {
+        &self.frames
+    }
+
+    /// Width and height in cells of the largest frame.
+    fn size(&self) -> (usize, usize) {
+        let width = self.frames.iter().map(|f| f.width()).max().unwrap_or(0);
+        let height = self.frames.iter().map(|f| f.height()).max().unwrap_or(0);
+        (width.max(1), height.max(1))
+    }
+
+    fn scale(&self) -> usize {
+        let (width, height) = self.size();
+        (TARGET_SIZE / width.max(height)).clamp(1, MAX_SCALE)
+    }
+
+    /// The palette indices of a frame, scaled up and padded to `width` by `height` pixels.
+    fn pixels(frame: &Grid<u8>, scale: usize, width: usize, height: usize) -> Vec<u8> {
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            pixels.extend((0..width).map(|x| frame.get((x / scale, y / scale)).copied().unwrap_or(0)));
+        }
+        pixels
+    }
+
+    /// Writes all frames as a looping GIF.
+    pub fn write_gif<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let scale = self.scale();
+        let (width, height) = self.size();
+        let (width, height) = (width * scale, height * scale);
+        if width > u16::MAX as usize || height > u16::MAX as usize {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "image too large for a GIF"));
+        }
+
+        // The colour table has 2^(bits) entries
+        let bits = (1..=8).find(|bits| 1 << bits >= self.palette.len()).unwrap();
+        out.write_all(b"GIF89a")?;
+        out.write_all(&(width as u16).to_le_bytes())?;
+        out.write_all(&(height as u16).to_le_bytes())?;
+        out.write_all(&[0xf0 | (bits as u8 - 1), 0, 0])?;
+        for idx in 0..1 << bits {
+            out.write_all(self.palette.get(idx).unwrap_or(&[0, 0, 0]))?;
+        }
+        if self.frames.len() > 1 {
+            // Loop forever
+            out.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+        }
+
+        let min_code_size = bits.max(2) as u8;
+        for frame in &self.frames {
+            out.write_all(&[0x21, 0xf9, 0x04, 0x04])?;
+            out.write_all(&self.delay.to_le_bytes())?;
+            out.write_all(&[0, 0])?;
+
+            out.write_all(&[0x2c, 0, 0, 0, 0])?;
+            out.write_all(&(width as u16).to_le_bytes())?;
+            out.write_all(&(height as u16).to_le_bytes())?;
+            out.write_all(&[0, min_code_size])?;
+            let data = lzw_encode(min_code_size, &Self::pixels(frame, scale, width, height));
+            for block in data.chunks(255) {
+                out.write_all(&[block.len() as u8])?;
+                out.write_all(block)?;
+            }
+            out.write_all(&[0])?;
+        }
+
+        out.write_all(&[0x3b])
+    }
+
+    /// Writes one frame as a binary PPM.
+    pub fn write_ppm<W: Write>(&self, frame: usize, out: &mut W) -> io::Result<()> {
+        let scale = self.scale();
+        let (width, height) = self.size();
+        let (width, height) = (width * scale, height * scale);
+
+        write!(out, "P6\n{} {}\n255\n", width, height)?;
+        for idx in Self::pixels(&self.frames[frame], scale, width, height) {
+            out.write_all(self.palette.get(idx as usize).unwrap_or(&[0, 0, 0]))?;
+        }
+        Ok(())
+    }
+
+    /// Saves the animation in the format that matches the extension of `path`: a GIF,
+    /// or for `.ppm` one PPM per frame, numbered if there is more than one. Returns the
+    /// names of the files written.
+    pub fn save(&self, path: &str) -> Result<Vec<String>, String> {
+        let create = |path: &str| File::create(path).map(BufWriter::new).map_err(|e| format!("could not create {}: {}", path, e));
+        let failed = |path: &str, e: io::Error| format!("could not write {}: {}", path, e);
+
+        match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+            Some("gif") => {
+                let mut out = create(path)?;
+                self.write_gif(&mut out).and_then(|_| out.flush()).map_err(|e| failed(path, e))?;
+                Ok(vec![path.to_string()])
+            }
+            Some("ppm") if self.frames.len() == 1 => {
+                let mut out = create(path)?;
+                self.write_ppm(0, &mut out).and_then(|_| out.flush()).map_err(|e| failed(path, e))?;
+                Ok(vec![path.to_string()])
+            }
+            Some("ppm") => {
+                let stem = &path[..path.len() - ".ppm".len()];
+                let mut paths = Vec::new();
+                for frame in 0..self.frames.len() {
+                    let path = format!("{}-{:04}.ppm", stem, frame + 1);
+                    let mut out = create(&path)?;
+                    self.write_ppm(frame, &mut out).and_then(|_| out.flush()).map_err(|e| failed(&path, e))?;
+                    paths.push(path);
+                }
+                Ok(paths)
+            }
+            _ => Err(format!("unknown image format for {}, expected .gif or .ppm", path)),
+        }
+    }
+}
+
+/// `steps` colours going evenly from `from` to `to`.
+pub fn gradient(from: Rgb, to: Rgb, steps: usize) -> Vec<Rgb> {
+    (0..steps)
+        .map(|step| {
+            let mix = |a: u8, b: u8| {
+                let t = if steps > 1 { step as i32 * 255 / (steps as i32 - 1) } else { 0 };
+                (a as i32 + (b as i32 - a as i32) * t / 255) as u8
+            };
+            [mix(from[0], to[0]), mix(from[1], to[1]), mix(from[2], to[2])]
+        })
+        .collect()
+}
+
+/// Packs codes of varying width, least significant bit first.
+struct BitWriter {
+    bytes: Vec<u8>,
+    acc: u32,
+    len: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u32) {
+        self.acc |= (code as u32) << self.len;
+        self.len += width;
+        while self.len >= 8 {
+            self.bytes.push(self.acc as u8);
+            self.acc >>= 8;
+            self.len -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.len > 0 {
+            self.bytes.push(self.acc as u8);
+        }
+        self.bytes
+    }
+}
+
+/// The variable width LZW compression of GIF image data.
+fn lzw_encode(min_code_size: u8, data: &[u8]) -> Vec<u8> {
+    let clear: u16 = 1 << min_code_size;
+    let end = clear + 1;
+    let mut out = BitWriter { bytes: Vec::new(), acc: 0, len: 0 };
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+    let mut width = min_code_size as u32 + 1;
+
+    out.write(clear, width);
+    let mut prefix: Option<u16> = None;
+    for &byte in data {
+        let code = match prefix {
+            None => {
+                prefix = Some(byte as u16);
+                continue;
+            }
+            Some(code) => code,
+        };
+        if let Some(&longer) = table.get(&(code, byte)) {
+            prefix = Some(longer);
+            continue;
+        }
+
+        out.write(code, width);
+        if next == 4096 {
+            // The table is full, start over
+            out.write(clear, width);
+            table.clear();
+            next = end + 1;
+            width = min_code_size as u32 + 1;
+        } else {
+            table.insert((code, byte), next);
+            next += 1;
+            if next > 1 << width && width < 12 {
+                width += 1;
+            }
+        }
+        prefix = Some(byte as u16);
+    }
+    if let Some(code) = prefix {
+        out.write(code, width);
+    }
+    out.write(end, width);
+
+    out.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::Grid;
+
+    /// A straightforward GIF LZW decoder, to check the encoder against.
+    fn lzw_decode(min_code_size: u8, data: &[u8]) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut width = min_code_size as usize + 1;
+        let mut prev: Option<Vec<u8>> = None;
+        let mut out = Vec::new();
+        let (mut acc, mut len, mut bytes) = (0usize, 0usize, data.iter());
+        loop {
+            while len < width {
+                acc |= (*bytes.next().expect("end code") as usize) << len;
+                len += 8;
+            }
+            let code = acc & ((1 << width) - 1);
+            acc >>= width;
+            len -= width;
+
+            if code == clear {
+                table = (0..clear).map(|c| vec![c as u8]).collect();
+                table.push(Vec::new());
+                table.push(Vec::new());
+                width = min_code_size as usize + 1;
+                prev = None;
+                continue;
+            }
+            if code == clear + 1 {
+                return out;
+            }
+            let entry = match (&prev, table.get(code)) {
+                (_, Some(entry)) => entry.clone(),
+                (Some(prev), None) => [prev.clone(), vec![prev[0]]].concat(),
+                (None, None) => panic!("Unknown first code {}", code),
+            };
+            if let Some(prev) = prev {
+                if table.len() < 4096 {
+                    table.push([prev, vec![entry[0]]].concat());
+                }
+            }
+            if table.len() == 1 << width && width < 12 {
+                width += 1;
+            }
+            out.extend(&entry);
+            prev = Some(entry);
+        }
+    }
+
+    #[test]
+    fn lzw() {
+        let mut rng = crate::rng::Rng::new(16);
+        for (min_code_size, len) in [(2, 0), (2, 1), (2, 100), (3, 10_000), (8, 50_000)] {
+            let data: Vec<u8> = (0..len).map(|_| rng.range(0..1 << min_code_size.min(3)) as u8).collect();
+            let encoded = super::lzw_encode(min_code_size, &data);
+            assert_eq!(lzw_decode(min_code_size, &encoded), data, "{} bit codes, {} bytes", min_code_size, len);
+        }
+    }
+
+    #[test]
+    fn gif() {
+        let mut anim = super::Animation::new(vec![[0, 0, 0], [255, 255, 255], [255, 0, 0]]);
+        anim.push_frame(Grid::from_vec(2, 1, vec![1, 2]));
+        anim.push_frame(Grid::from_vec(1, 2, vec![2, 1]));
+        let mut out = Vec::new();
+        anim.write_gif(&mut out).unwrap();
+
+        // Two by two cells of 16 by 16 pixels each
+        assert_eq!(&out[..10], b"GIF89a\x20\x00\x20\x00");
+        assert_eq!(out[10], 0xf1);
+        assert!(out.windows(11).any(|w| w == b"NETSCAPE2.0"));
+        assert_eq!(out[out.len() - 1], 0x3b);
+    }
+
+    #[test]
+    fn ppm() {
+        let mut anim = super::Animation::new(vec![[0, 0, 0], [255, 128, 0]]);
+        anim.push_frame(Grid::from_vec(1, 1, vec![1]));
+        let mut out = Vec::new();
+        anim.write_ppm(0, &mut out).unwrap();
+        assert!(out.starts_with(b"P6\n16 16\n255\n"));
+        assert_eq!(out.len(), 13 + 16 * 16 * 3);
+        assert_eq!(&out[13..16], &[255, 128, 0]);
+    }
+
+    #[test]
+    fn gradient() {
+        assert_eq!(super::gradient([0, 0, 0], [255, 100, 10], 3), vec![[0, 0, 0], [127, 49, 4], [255, 100, 10]]);
+        assert_eq!(super::gradient([9, 9, 9], [0, 0, 0], 1), vec![[9, 9, 9]]);
+    }
+}
@@ -0,0 +1,69 @@
+//! Exploring a puzzle with `aoc repl`: a day turns its parsed input into a `Session`,
+//! which runs commands like `step 10` on its own copy of the state and shows the result.
+
+use std::ops::RangeInclusive;
+
+use crate::params;
+
+/// A command a session accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Command {
+    pub name: &'static str,
+    /// What follows the name, like `N` or `x=N|y=N`; empty if nothing does
+    pub args: &'static str,
+    pub help: &'static str,
+}
+
+/// The state of a puzzle being explored.
+pub trait Session {
+    /// The commands `run` accepts.
+    fn commands(&self) -> &'static [Command];
+
+    /// Runs command `name`, one of `commands`, with the rest of the line as `args`;
+    /// returns what to print.
+    fn run(&mut self, name: &str, args: &str) -> Result<String, String>;
+
+    /// The current state.
+    fn show(&self) -> String;
+}
+
+/// Lists `commands` with their arguments, one per line.
+pub fn describe(commands: &[Command]) -> String {
+    commands
+        .iter()
+        .map(|c| format!("  {:<16} {}\n", format!("{} {}", c.name, c.args).trim_end(), c.help))
+        .collect()
+}
+
+/// `args` of command `name` as a number of times to repeat it, within `range`; once if
+/// there are no arguments.
+pub fn repeat(name: &str, args: &str, range: RangeInclusive<usize>) -> Result<usize, String> {
+    if args.is_empty() {
+        return Ok(1);
+    }
+    params::number(name, args, range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Command;
+
+    #[test]
+    fn describe() {
+        let commands = [
+            Command { name: "step", args: "[N]", help: "runs N steps" },
+            Command { name: "path", args: "", help: "finds the path" },
+        ];
+        assert_eq!(super::describe(&commands), "  step [N]         runs N steps\n  path             finds the path\n");
+    }
+
+    #[test]
+    fn repeat() {
+        assert_eq!(super::repeat("step", "", 1..=100), Ok(1));
+        assert_eq!(super::repeat("step", "10", 1..=100), Ok(10));
+        assert_eq!(
+            super::repeat("step", "0", 1..=100),
+            Err(String::from("invalid value '0' for step, expected a number from 1 to 100"))
+        );
+    }
+}
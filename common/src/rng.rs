@@ -0,0 +1,83 @@
+use std::ops::Range;
+
+/// A small deterministic pseudo random number generator (SplitMix64), so generated
+/// inputs and test cases can be reproduced from their seed on every platform.
+///
+/// Not suitable for anything but generating puzzle-like data.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, which must not be empty. The slight modulo bias does not
+    /// matter for the sizes used here.
+    pub fn range(&mut self, range: Range<usize>) -> usize {
+        assert!(range.start < range.end, "Empty range {:?}", range);
+        range.start + (self.next_u64() % (range.end - range.start) as u64) as usize
+    }
+
+    /// Like `range`, for signed numbers.
+    pub fn range_i64(&mut self, range: Range<i64>) -> i64 {
+        assert!(range.start < range.end, "Empty range {:?}", range);
+        range.start + (self.next_u64() % range.start.abs_diff(range.end)) as i64
+    }
+
+    /// True with a chance of `numerator` in `denominator`.
+    pub fn chance(&mut self, numerator: usize, denominator: usize) -> bool {
+        self.range(0..denominator) < numerator
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for idx in (1..items.len()).rev() {
+            items.swap(idx, self.range(0..idx + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn deterministic() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn ranges() {
+        let mut rng = Rng::new(7);
+        let mut seen = [false; 5];
+        for _ in 0..200 {
+            seen[rng.range(3..8) - 3] = true;
+            assert!((-5..5).contains(&rng.range_i64(-5..5)));
+        }
+        assert!(seen.iter().all(|s| *s));
+
+        let mut items: Vec<usize> = (0..10).collect();
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, (0..10).collect::<Vec<usize>>());
+    }
+}
@@ -0,0 +1,304 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// How much work a search did, to compare approaches on the same puzzle.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Nodes that were expanded, i.e. whose neighbors were enumerated
+    pub visited: usize,
+    /// Nodes that were reached: distinct nodes for the shortest path searches, every
+    /// extension of the current path for `dfs_paths`
+    pub discovered: usize,
+}
+
+/// A path from the start to a goal, both included, with its total cost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+/// Bookkeeping for nodes seen so far; nodes are numbered in the order they are discovered.
+struct Discovered<N, C> {
+    index: HashMap<N, usize>,
+    nodes: Vec<N>,
+    cost: Vec<C>,
+    parent: Vec<Option<usize>>,
+}
+
+impl<N: Clone + Eq + Hash, C> Discovered<N, C> {
+    fn new() -> Discovered<N, C> {
+        Discovered { index: HashMap::new(), nodes: Vec::new(), cost: Vec::new(), parent: Vec::new() }
+    }
+
+    fn insert(&mut self, node: N, cost: C, parent: Option<usize>) -> usize {
+        let idx = self.nodes.len();
+        self.index.insert(node.clone(), idx);
+        self.nodes.push(node);
+        self.cost.push(cost);
+        self.parent.push(parent);
+        idx
+    }
+
+    fn path(&self, mut idx: usize) -> Vec<N> {
+        let mut nodes = vec![self.nodes[idx].clone()];
+        while let Some(parent) = self.parent[idx] {
+            nodes.push(self.nodes[parent].clone());
+            idx = parent;
+        }
+        nodes.reverse();
+        nodes
+    }
+}
+
+/// Entry of the open set; ordered so the `BinaryHeap` pops the lowest estimate first.
+struct Open<C> {
+    estimate: C,
+    cost: C,
+    idx: usize,
+}
+
+impl<C: Ord> PartialEq for Open<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<C: Ord> Eq for Open<C> {}
+
+impl<C: Ord> PartialOrd for Open<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: Ord> Ord for Open<C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed: lowest estimate first, then prefer the node that got furthest
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+/// A* search from `start` to the first node for which `is_goal` holds.
+///
+/// `neighbors` returns the nodes reachable from a node with the cost of that step and
+/// `heuristic` must never overestimate the remaining cost to a goal.
+pub fn astar<N, C, FN, IN, FH, FG>(start: N, mut neighbors: FN, mut heuristic: FH, mut is_goal: FG) -> (Option<Path<N, C>>, Stats)
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FG: FnMut(&N) -> bool,
+{
+    let mut stats = Stats::default();
+    let mut seen: Discovered<N, C> = Discovered::new();
+    let mut open: BinaryHeap<Open<C>> = BinaryHeap::new();
+
+    let estimate = heuristic(&start);
+    let idx = seen.insert(start, C::default(), None);
+    open.push(Open { estimate, cost: C::default(), idx });
+
+    while let Some(Open { cost, idx, .. }) = open.pop() {
+        if cost > seen.cost[idx] {
+            // Stale entry, a cheaper way to this node was found after it was pushed
+            continue;
+        }
+        if is_goal(&seen.nodes[idx]) {
+            stats.discovered = seen.nodes.len();
+            return (Some(Path { nodes: seen.path(idx), cost }), stats);
+        }
+
+        stats.visited += 1;
+        for (next, step) in neighbors(&seen.nodes[idx]) {
+            let next_cost = cost + step;
+            let next_idx = match seen.index.get(&next) {
+                Some(&next_idx) if seen.cost[next_idx] <= next_cost => continue,
+                Some(&next_idx) => {
+                    seen.cost[next_idx] = next_cost;
+                    seen.parent[next_idx] = Some(idx);
+                    next_idx
+                }
+                None => seen.insert(next.clone(), next_cost, Some(idx)),
+            };
+            open.push(Open { estimate: next_cost + heuristic(&next), cost: next_cost, idx: next_idx });
+        }
+    }
+
+    stats.discovered = seen.nodes.len();
+    (None, stats)
+}
+
+/// Dijkstra's shortest path: A* without a heuristic.
+pub fn dijkstra<N, C, FN, IN, FG>(start: N, neighbors: FN, is_goal: FG) -> (Option<Path<N, C>>, Stats)
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FG: FnMut(&N) -> bool,
+{
+    astar(start, neighbors, |_| C::default(), is_goal)
+}
+
+/// Breadth-first search for the path with the fewest steps from `start` to a goal.
+pub fn bfs<N, FN, IN, FG>(start: N, mut neighbors: FN, mut is_goal: FG) -> (Option<Vec<N>>, Stats)
+where
+    N: Clone + Eq + Hash,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FG: FnMut(&N) -> bool,
+{
+    let mut stats = Stats::default();
+    let mut seen: Discovered<N, ()> = Discovered::new();
+    let mut queue: VecDeque<usize> = VecDeque::new();
+
+    queue.push_back(seen.insert(start, (), None));
+    while let Some(idx) = queue.pop_front() {
+        if is_goal(&seen.nodes[idx]) {
+            stats.discovered = seen.nodes.len();
+            return (Some(seen.path(idx)), stats);
+        }
+
+        stats.visited += 1;
+        for next in neighbors(&seen.nodes[idx]) {
+            if !seen.index.contains_key(&next) {
+                queue.push_back(seen.insert(next, (), Some(idx)));
+            }
+        }
+    }
+
+    stats.discovered = seen.nodes.len();
+    (None, stats)
+}
+
+/// Depth-first enumeration of all paths from `start` that end in a goal node.
+///
+/// `successors` gets the path so far (ending in the current node) and returns the
+/// nodes to continue with, so it decides which revisits are allowed. Paths end at the
+/// first goal node they reach.
+pub fn dfs_paths<N, FN, IN, FG>(start: N, mut successors: FN, mut is_goal: FG) -> (Vec<Vec<N>>, Stats)
+where
+    N: Clone,
+    FN: FnMut(&[N]) -> IN,
+    IN: IntoIterator<Item = N>,
+    FG: FnMut(&N) -> bool,
+{
+    let mut stats = Stats::default();
+    let mut paths: Vec<Vec<N>> = Vec::new();
+    let mut path: Vec<N> = vec![start];
+
+    if is_goal(&path[0]) {
+        stats.discovered = 1;
+        return (vec![path], stats);
+    }
+
+    // Stack of the nodes still to try at each depth of the current path
+    let mut pending: Vec<Vec<N>> = Vec::new();
+    stats.visited += 1;
+    stats.discovered += 1;
+    pending.push(successors(&path).into_iter().collect());
+
+    while let Some(candidates) = pending.last_mut() {
+        let next = match candidates.pop() {
+            Some(next) => next,
+            None => {
+                pending.pop();
+                path.pop();
+                continue;
+            }
+        };
+
+        stats.discovered += 1;
+        path.push(next);
+        if is_goal(path.last().unwrap()) {
+            paths.push(path.clone());
+            path.pop();
+        } else {
+            stats.visited += 1;
+            let next_candidates = successors(&path).into_iter().collect();
+            pending.push(next_candidates);
+        }
+    }
+
+    (paths, stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::Grid;
+
+    fn maze() -> Grid<char> {
+        let lines: Vec<String> = ["..#....", ".##.##.", "....#..", ".#.##.#", "...#..."]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        crate::parse::char_grid(&lines, "a maze cell", Some).unwrap()
+    }
+
+    #[test]
+    fn astar_and_dijkstra() {
+        let grid = crate::parse::digit_grid::<u32>(
+            &["1163751742", "1381373672", "2136511328", "3694931569", "7463417111"]
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<String>>(),
+            "a digit",
+        )
+        .unwrap();
+        let goal = (grid.width() - 1, grid.height() - 1);
+        let neighbors = |pos: &(usize, usize)| grid.neighbors4(*pos).map(|n| (n, grid[n])).collect::<Vec<_>>();
+
+        let (path, dijkstra_stats) = super::dijkstra((0, 0), neighbors, |pos| *pos == goal);
+        let path = path.unwrap();
+        assert_eq!(path.nodes.first(), Some(&(0, 0)));
+        assert_eq!(path.nodes.last(), Some(&goal));
+        assert_eq!(path.nodes.iter().skip(1).map(|pos| grid[*pos]).sum::<u32>(), path.cost);
+
+        let manhattan = |pos: &(usize, usize)| ((goal.0 - pos.0) + (goal.1 - pos.1)) as u32;
+        let (astar_path, astar_stats) = super::astar((0, 0), neighbors, manhattan, |pos| *pos == goal);
+        assert_eq!(astar_path.unwrap().cost, path.cost);
+        assert!(astar_stats.visited <= dijkstra_stats.visited);
+    }
+
+    #[test]
+    fn bfs() {
+        let grid = maze();
+        let open = |pos: &(usize, usize)| grid.neighbors4(*pos).filter(|n| grid[*n] == '.').collect::<Vec<_>>();
+
+        let (path, stats) = super::bfs((0, 0), open, |pos| *pos == (6, 4));
+        let path = path.unwrap();
+        assert_eq!(path.len(), 17);
+        assert!(stats.visited <= stats.discovered);
+
+        let (path, _) = super::bfs((0, 0), open, |pos| *pos == (2, 0));
+        assert_eq!(path, None);
+    }
+
+    #[test]
+    fn dfs_paths() {
+        // start -> a, start -> b, a -> b, a -> end, b -> end
+        let edges = [("start", "a"), ("start", "b"), ("a", "b"), ("a", "end"), ("b", "end")];
+        let successors = |path: &[&'static str]| {
+            let cur = *path.last().unwrap();
+            edges
+                .iter()
+                .filter(|(from, _)| *from == cur)
+                .map(|(_, to)| *to)
+                .collect::<Vec<_>>()
+        };
+        let (mut paths, stats) = super::dfs_paths("start", successors, |cave| *cave == "end");
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![vec!["start", "a", "b", "end"], vec!["start", "a", "end"], vec!["start", "b", "end"]]
+        );
+        assert_eq!(stats.visited, 4);
+    }
+}
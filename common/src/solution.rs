@@ -0,0 +1,393 @@
+use std::any::Any;
+use std::env;
+use std::fmt;
+use std::process;
+
+use crate::bigint::BigUint;
+use crate::error::AocError;
+use crate::input::{parse_input_arg, InputSource, LineReader};
+use crate::params::Param;
+use crate::render::Animation;
+use crate::repl::Session;
+use crate::rng::Rng;
+use crate::visual::Frame;
+
+/// The answer to one part of a puzzle: either a number or (multi-line) text, or why
+/// there is none.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    /// The part could not be solved, like when the dots on the paper are not letters.
+    /// Says why, possibly followed by what was found instead. It is not an answer: the
+    /// runner reports it as a failure and never compares or records it.
+    Unsolved(String),
+}
+
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        match self {
+            Answer::Number(_) => false,
+            Answer::Text(text) | Answer::Unsolved(text) => text.trim_end().contains('\n'),
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        !matches!(self, Answer::Unsolved(_))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(num) => write!(f, "{}", num),
+            Answer::Text(text) | Answer::Unsolved(text) => write!(f, "{}", text.trim_end()),
+        }
+    }
+}
+
+macro_rules! answer_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(num: $t) -> Answer {
+                    Answer::Number(num as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_number!(i32, u32, i64, u64, isize, usize);
+
+/// A number when it fits, otherwise its digits as text.
+impl From<BigUint> for Answer {
+    fn from(num: BigUint) -> Answer {
+        match num.to_u128().and_then(|num| i128::try_from(num).ok()) {
+            Some(num) => Answer::Number(num),
+            None => Answer::Text(num.to_string()),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Answer {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Answer {
+        Answer::Text(text.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "one"),
+            Part::Two => write!(f, "two"),
+        }
+    }
+}
+
+/// A day's puzzle: parse the input once, then solve both parts from the parsed form.
+pub trait Solution {
+    type Input;
+
+    /// The example from the puzzle description, used for `--example`.
+    fn example(&self) -> &'static str;
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, AocError>;
+
+    fn part_one(&self, input: &Self::Input) -> Answer;
+
+    fn part_two(&self, input: &Self::Input) -> Answer;
+
+    /// Solves both parts in a single pass over the lines, for inputs too big to read
+    /// into memory first. `None` if the day can not do that.
+    fn solve_stream(&self, _lines: &mut LineReader) -> Option<Result<(Answer, Answer), AocError>> {
+        None
+    }
+
+    /// A random well-formed input, roughly `size` items big, for stress testing.
+    /// `None` if the day has no generator.
+    fn generate(&self, _rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+
+    /// Pictures of how the puzzle is solved, a frame per step of the simulation.
+    /// `None` if the day has nothing to show.
+    fn render(&self, _input: &Self::Input) -> Option<Animation> {
+        None
+    }
+
+    /// The states of the simulation as text, to play back in the terminal. `None` if
+    /// the day is not a simulation.
+    fn visualize(&self, _input: &Self::Input) -> Option<Vec<Frame>> {
+        None
+    }
+
+    /// The parameters `set_param` accepts, like the number of days to simulate.
+    fn params(&self) -> &'static [Param] {
+        &[]
+    }
+
+    /// Changes parameter `name`, one of `params`, after checking `value`.
+    fn set_param(&mut self, name: &str, _value: &str) -> Result<(), String> {
+        Err(format!("unknown parameter '{}'", name))
+    }
+
+    /// A copy of the input to explore with commands in `aoc repl`. `None` if the day has
+    /// no commands.
+    fn session(&self, _input: &Self::Input) -> Option<Box<dyn Session>> {
+        None
+    }
+}
+
+/// Object safe view on a `Solution`, so tools can keep all days in one list.
+pub trait Puzzle {
+    fn example_data(&self) -> &'static str;
+
+    fn parse_input(&self, lines: &[String]) -> Result<Box<dyn Any>, AocError>;
+
+    fn solve(&self, input: &dyn Any, part: Part) -> Answer;
+
+    fn stream(&self, lines: &mut LineReader) -> Option<Result<(Answer, Answer), AocError>>;
+
+    fn generate_input(&self, rng: &mut Rng, size: usize) -> Option<String>;
+
+    fn render_input(&self, input: &dyn Any) -> Option<Animation>;
+
+    fn visualize_input(&self, input: &dyn Any) -> Option<Vec<Frame>>;
+
+    fn parameters(&self) -> &'static [Param];
+
+    fn set_parameter(&mut self, name: &str, value: &str) -> Result<(), String>;
+
+    fn start_session(&self, input: &dyn Any) -> Option<Box<dyn Session>>;
+}
+
+impl<S> Puzzle for S
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    fn example_data(&self) -> &'static str {
+        self.example()
+    }
+
+    fn parse_input(&self, lines: &[String]) -> Result<Box<dyn Any>, AocError> {
+        let input = self.parse(lines)?;
+        Ok(Box::new(input))
+    }
+
+    fn solve(&self, input: &dyn Any, part: Part) -> Answer {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("Input was parsed by another puzzle");
+        match part {
+            Part::One => self.part_one(input),
+            Part::Two => self.part_two(input),
+        }
+    }
+
+    fn stream(&self, lines: &mut LineReader) -> Option<Result<(Answer, Answer), AocError>> {
+        self.solve_stream(lines)
+    }
+
+    fn generate_input(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        self.generate(rng, size)
+    }
+
+    fn render_input(&self, input: &dyn Any) -> Option<Animation> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("Input was parsed by another puzzle");
+        self.render(input)
+    }
+
+    fn visualize_input(&self, input: &dyn Any) -> Option<Vec<Frame>> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("Input was parsed by another puzzle");
+        self.visualize(input)
+    }
+
+    fn parameters(&self) -> &'static [Param] {
+        self.params()
+    }
+
+    fn set_parameter(&mut self, name: &str, value: &str) -> Result<(), String> {
+        self.set_param(name, value)
+    }
+
+    fn start_session(&self, input: &dyn Any) -> Option<Box<dyn Session>> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("Input was parsed by another puzzle");
+        self.session(input)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct DayArgs {
+    source: InputSource,
+    stream: bool,
+}
+
+fn parse_day_args(args: &[String]) -> Result<DayArgs, String> {
+    let mut source = InputSource::File(String::from("input"));
+    let mut stream = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--stream" {
+            stream = true;
+        } else {
+            source = parse_input_arg(arg, &mut iter)?.ok_or(format!("unknown argument '{}'", arg))?;
+        }
+    }
+
+    Ok(DayArgs { source, stream })
+}
+
+fn solve_day<S: Solution>(solution: &S, args: &DayArgs) -> Result<[Answer; 2], String> {
+    let source = &args.source;
+    if args.stream {
+        let mut lines = source.open(solution.example())?;
+        let result = solution.solve_stream(&mut lines);
+        if let Some(e) = lines.take_error() {
+            return Err(format!("could not read {}: {}", source.name(), e));
+        }
+        let (one, two) = result
+            .ok_or("this day can not stream its input")?
+            .map_err(|e| format!("{}:{}", source.name(), e))?;
+        return Ok([one, two]);
+    }
+
+    let lines = source.read_lines(solution.example())?;
+    let input = solution
+        .parse(&lines)
+        .map_err(|e| format!("{}:{}", source.name(), e))?;
+    Ok([solution.part_one(&input), solution.part_two(&input)])
+}
+
+/// Shared `main()` of the day crates: solve both parts for `./input`, or for the
+/// input selected with `--input <path>`, `-` (stdin) or `--example`. With `--stream`
+/// the input is solved in a single pass without reading it into memory first.
+pub fn run_day<S: Solution>(solution: &S) {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args = match parse_day_args(&args) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!(
+                "Usage: {} [--input <path> | - | --example] [--stream]",
+                env::args().next().unwrap_or_default()
+            );
+            process::exit(2);
+        }
+    };
+
+    let answers = match solve_day(solution, &args) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+    let mut unsolved = false;
+    for (part, ans) in Part::BOTH.iter().zip(answers) {
+        if !ans.is_solved() {
+            eprintln!("Star {}: NOT SOLVED: {}", part, ans);
+            unsolved = true;
+        } else if ans.is_multiline() {
+            println!("Star {}:\n{}", part, ans);
+        } else {
+            println!("Star {}: {}", part, ans);
+        }
+    }
+    if unsolved {
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Answer, AocError, InputSource, LineReader, Part, Puzzle, Solution};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u32>;
+
+        fn example(&self) -> &'static str {
+            "1\n2"
+        }
+
+        fn parse(&self, lines: &[String]) -> Result<Vec<u32>, AocError> {
+            lines
+                .iter()
+                .enumerate()
+                .map(|(idx, l)| l.parse::<u32>().map_err(|_| AocError::at(idx + 1, l, l, "a number")))
+                .collect()
+        }
+
+        fn part_one(&self, input: &Vec<u32>) -> Answer {
+            input.iter().sum::<u32>().into()
+        }
+
+        fn part_two(&self, input: &Vec<u32>) -> Answer {
+            format!("{}\n{}", input[0], input[1]).into()
+        }
+    }
+
+    #[test]
+    fn puzzle() {
+        let lines: Vec<String> = vec![String::from("3"), String::from("4")];
+        let puzzle: &dyn Puzzle = &Sum;
+        let input = puzzle.parse_input(&lines).unwrap();
+        assert_eq!(puzzle.solve(&*input, Part::One), Answer::Number(7));
+        assert_eq!(puzzle.solve(&*input, Part::Two), Answer::Text(String::from("3\n4")));
+        assert!(puzzle.solve(&*input, Part::Two).is_multiline());
+        assert_eq!(puzzle.example_data(), "1\n2");
+        assert!(puzzle.stream(&mut LineReader::new(std::io::Cursor::new("1"))).is_none());
+
+        let lines: Vec<String> = vec![String::from("3"), String::from("x")];
+        let err = puzzle.parse_input(&lines).err().unwrap();
+        assert_eq!(err.to_string(), "2:1: expected a number, found 'x'");
+    }
+
+    #[test]
+    fn parse_day_args() {
+        let args = |line: &str| -> Vec<String> { line.split_whitespace().map(|x| x.to_string()).collect() };
+        let source = |line: &str| super::parse_day_args(&args(line)).map(|a| a.source);
+        assert_eq!(source(""), Ok(InputSource::File(String::from("input"))));
+        assert_eq!(source("--input other"), Ok(InputSource::File(String::from("other"))));
+        assert_eq!(source("-"), Ok(InputSource::Stdin));
+        assert_eq!(source("--example"), Ok(InputSource::Example));
+        assert_eq!(
+            super::parse_day_args(&args("--example --stream")),
+            Ok(super::DayArgs { source: InputSource::Example, stream: true })
+        );
+        assert!(super::parse_day_args(&args("--part 1")).is_err());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Answer::from(-3isize).to_string(), "-3");
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::from("#.\n.#\n").to_string(), "#.\n.#");
+    }
+}
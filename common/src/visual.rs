@@ -0,0 +1,100 @@
+//! Text frames for watching a simulation step by step in the terminal, with the
+//! `--visualize` option of the runner. Colours are ANSI 256-colour codes.
+
+use std::fmt::Display;
+
+use crate::bigint::BigUint;
+use crate::grid::Grid;
+
+/// One state of a simulation: a caption like "Step 12" and the text showing the state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub caption: String,
+    pub text: String,
+}
+
+impl Frame {
+    pub fn new(caption: impl Into<String>, text: impl Into<String>) -> Frame {
+        Frame { caption: caption.into(), text: text.into() }
+    }
+}
+
+pub const RESET: &str = "\x1b[0m";
+
+/// `text` in foreground colour `colour`.
+pub fn paint(text: &str, colour: u8) -> String {
+    format!("\x1b[38;5;{}m{}{}", colour, text, RESET)
+}
+
+/// The cells of `grid` as lines of coloured characters; `cell` gives the character
+/// and colour of each value.
+pub fn grid_text<T, F>(grid: &Grid<T>, mut cell: F) -> String
+where
+    F: FnMut(&T) -> (char, u8),
+{
+    let mut text = String::new();
+    for row in grid.rows() {
+        let mut current: Option<u8> = None;
+        for value in row {
+            let (ch, colour) = cell(value);
+            if current != Some(colour) {
+                text.push_str(&format!("\x1b[38;5;{}m", colour));
+                current = Some(colour);
+            }
+            text.push(ch);
+        }
+        text.push_str(RESET);
+        text.push('\n');
+    }
+    text
+}
+
+/// A line with `label`, a bar of up to `width` blocks showing `value` relative to
+/// `max`, and the value itself.
+pub fn bar(label: &str, value: u64, max: u64, width: usize, colour: u8) -> String {
+    let len = if max == 0 { 0 } else { (value as u128 * width as u128 / max as u128) as usize };
+    bar_line(label, len, width, colour, value)
+}
+
+/// Like `bar`, for numbers of any size.
+pub fn big_bar(label: &str, value: &BigUint, max: &BigUint, width: usize, colour: u8) -> String {
+    // The top 64 bits are plenty to get the length of the bar right
+    let shift = max.bits().saturating_sub(64);
+    let top = |num: &BigUint| (num >> shift).to_u128().unwrap_or(0) as u64;
+    let len = if max.is_zero() { 0 } else { (top(value) as u128 * width as u128 / top(max) as u128) as usize };
+    bar_line(label, len, width, colour, value)
+}
+
+fn bar_line(label: &str, len: usize, width: usize, colour: u8, value: impl Display) -> String {
+    format!("{} {}{} {}\n", label, paint(&"█".repeat(len), colour), " ".repeat(width - len), value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bigint::BigUint;
+    use crate::grid::Grid;
+
+    #[test]
+    fn grid_text() {
+        let grid = Grid::from_vec(3, 1, vec![1, 1, 2]);
+        let text = super::grid_text(&grid, |v| (if *v == 1 { '#' } else { '.' }, *v as u8));
+        assert_eq!(text, "\x1b[38;5;1m##\x1b[38;5;2m.\x1b[0m\n");
+    }
+
+    #[test]
+    fn bar() {
+        assert_eq!(super::bar("a", 5, 10, 4, 3), "a \x1b[38;5;3m██\x1b[0m   5\n");
+        assert_eq!(super::bar("b", 0, 0, 2, 3), "b \x1b[38;5;3m\x1b[0m   0\n");
+    }
+
+    #[test]
+    fn big_bar() {
+        let mut max = BigUint::from(1u64);
+        for _ in 0..100 {
+            max = max.clone() + &max;
+        }
+        let text = super::big_bar("a", &(&max >> 1), &max, 4, 3);
+        assert_eq!(text, "a \x1b[38;5;3m██\x1b[0m   633825300114114700748351602688\n");
+        assert_eq!(super::big_bar("b", &BigUint::from(5u64), &BigUint::from(10u64), 4, 3), super::bar("b", 5, 10, 4, 3));
+    }
+}
@@ -0,0 +1,171 @@
+//! Day 1: Sonar Sweep. Counts how often the depth measurements of the sonar
+//! increase, one by one and in sliding windows of three.
+
+#![warn(missing_docs)]
+
+use std::collections::VecDeque;
+use std::vec::Vec;
+
+use common::{parse, Answer, AocError, LineReader, Solution};
+
+fn parse_line(line_nr: usize, line: &str) -> Result<isize, AocError> {
+    parse::number(line_nr, line, line, "a depth number")
+}
+
+/// The depth measurements, one per line.
+pub fn parse(lines: &[String]) -> Result<Vec<isize>, AocError> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| parse_line(idx + 1, line))
+        .collect()
+}
+
+/// Counts depth increases one measurement at a time, keeping only the last three depths.
+#[derive(Default)]
+pub struct Increases {
+    last: VecDeque<isize>,
+    /// Measurements deeper than the one before
+    pub single: usize,
+    /// Three-measurement windows deeper than the window before
+    pub window: usize,
+}
+
+impl Increases {
+    /// Counts the increases that the next measurement makes.
+    pub fn add(&mut self, depth: isize) {
+        if let Some(&prev) = self.last.back() {
+            if depth > prev {
+                self.single += 1;
+            }
+        }
+
+        // The windows share two depths, so the new one only has to be deeper than the
+        // one dropped, like in `star_two`; adding them up could overflow
+        if self.last.len() == 3 && depth > self.last.pop_front().unwrap() {
+            self.window += 1;
+        }
+        self.last.push_back(depth);
+    }
+}
+
+/// Number of measurements deeper than the one before.
+pub fn star_one(depths: &[isize]) -> usize {
+    let mut increases = Increases::default();
+    for &depth in depths {
+        increases.add(depth);
+    }
+
+    increases.single
+}
+
+/// Number of three-measurement windows deeper than the window before.
+pub fn star_two(depths: &[isize]) -> usize {
+    depths
+        .windows(4)
+        // Sliding window comparison with dropped terms: el[0] + el[1] + el[2] < el[1] + el[2] + el[3] <=> el[0] < el[3]
+        .filter(|w| w[0] < w[3])
+        .count()
+}
+
+/// Both stars in a single pass, keeping only the last three depths in memory.
+pub fn solve_stream(lines: &mut LineReader) -> Result<(usize, usize), AocError> {
+    let mut increases = Increases::default();
+    while let Some((line_nr, line)) = lines.next_line() {
+        increases.add(parse_line(line_nr, line)?);
+    }
+
+    Ok((increases.single, increases.window))
+}
+
+/// The example from the puzzle description
+pub static TEST_DATA: &str = include_str!("../../examples/day01/example.txt");
+
+/// The puzzle, for the runner.
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<isize>;
+
+    fn example(&self) -> &'static str {
+        TEST_DATA
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Vec<isize>, AocError> {
+        parse(lines)
+    }
+
+    fn part_one(&self, depths: &Vec<isize>) -> Answer {
+        star_one(depths).into()
+    }
+
+    fn part_two(&self, depths: &Vec<isize>) -> Answer {
+        star_two(depths).into()
+    }
+
+    fn solve_stream(&self, lines: &mut LineReader) -> Option<Result<(Answer, Answer), AocError>> {
+        Some(solve_stream(lines).map(|(one, two)| (one.into(), two.into())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TEST_DATA;
+
+    #[test]
+    fn test_star_one() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let ans = super::star_one(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 7);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let ans = super::star_two(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 5);
+    }
+
+    #[test]
+    fn stream() {
+        let mut lines = common::LineReader::new(std::io::Cursor::new(TEST_DATA));
+
+        let ans = super::solve_stream(&mut lines).unwrap();
+        assert_eq!(ans, (7, 5));
+    }
+
+    #[test]
+    fn increases_match_windows() {
+        common::prop::check(
+            300,
+            |rng, size| (0..rng.range(0..size * 2 + 1)).map(|_| rng.range_i64(0..200) as isize).collect::<Vec<isize>>(),
+            |depths| {
+                let mut increases = super::Increases::default();
+                for &depth in depths {
+                    increases.add(depth);
+                }
+
+                let sums: Vec<isize> = depths.windows(3).map(|w| w.iter().sum()).collect();
+                let naive = sums.windows(2).filter(|w| w[0] < w[1]).count();
+                common::prop::equal(increases.window, naive, "window increases")?;
+                common::prop::equal(super::star_two(depths), naive, "window increases")?;
+                let single = depths.windows(2).filter(|w| w[0] < w[1]).count();
+                common::prop::equal(increases.single, single, "single increases")
+            },
+        );
+    }
+
+    #[test]
+    fn deepest_depths() {
+        let max = isize::MAX;
+        let depths = [max, max, 1, 1, max - 1, max];
+        let mut increases = super::Increases::default();
+        for &depth in &depths {
+            increases.add(depth);
+        }
+        assert_eq!((increases.single, increases.window), (2, 1));
+        assert_eq!((super::star_one(&depths), super::star_two(&depths)), (2, 1));
+    }
+}
@@ -0,0 +1,245 @@
+//! Day 2: Dive! Follows the commands that steer the submarine, which mean something
+//! different in each part.
+
+#![warn(missing_docs)]
+
+use std::vec::Vec;
+
+use common::{parse, Answer, AocError, LineReader, Solution};
+
+/// A command with the number of units to move.
+#[derive(Debug)]
+pub enum Direction {
+    /// `forward X`
+    Forward(isize),
+    /// `down X`
+    Down(isize),
+    /// `up X`
+    Up(isize),
+}
+
+fn parse_line(line_nr: usize, line: &str) -> Result<Direction, AocError> {
+    let mut split = line.split(' ');
+    let command = split.next().unwrap_or(line);
+    let value = split
+        .next()
+        .ok_or_else(|| AocError::at_end(line_nr, line, "' ' and a number after the command"))?;
+    let what = "a number of at least 0";
+    let val = match parse::number(line_nr, line, value, what)? {
+        val if val >= 0 => val,
+        _ => return Err(AocError::at(line_nr, line, value, what)),
+    };
+    if let Some(rest) = split.next() {
+        return Err(AocError::at(line_nr, line, rest, "end of line"));
+    }
+
+    match command {
+        "forward" => Ok(Direction::Forward(val)),
+        "up" => Ok(Direction::Up(val)),
+        "down" => Ok(Direction::Down(val)),
+        _ => Err(AocError::at(line_nr, line, command, "'forward', 'up' or 'down'")),
+    }
+}
+
+/// The commands, one per line.
+pub fn parse(lines: &[String]) -> Result<Vec<Direction>, AocError> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| parse_line(idx + 1, line))
+        .collect()
+}
+
+/// Position of the submarine under both readings of the commands: for star one up and
+/// down change the depth, for star two they change the aim. A value that no longer fits
+/// in an i128 is `None`.
+pub struct Position {
+    horizontal: Option<i128>,
+    depth: Option<i128>,
+    aim: Option<i128>,
+    aimed_depth: Option<i128>,
+}
+
+impl Default for Position {
+    fn default() -> Position {
+        Position { horizontal: Some(0), depth: Some(0), aim: Some(0), aimed_depth: Some(0) }
+    }
+}
+
+fn add(acc: Option<i128>, val: Option<i128>) -> Option<i128> {
+    acc?.checked_add(val?)
+}
+
+fn mul(acc: Option<i128>, val: Option<i128>) -> Option<i128> {
+    acc?.checked_mul(val?)
+}
+
+impl Position {
+    /// Moves according to one command.
+    pub fn apply(&mut self, dir: &Direction) {
+        match *dir {
+            Direction::Forward(val) => {
+                let val = Some(val as i128);
+                self.horizontal = add(self.horizontal, val);
+                self.aimed_depth = add(self.aimed_depth, mul(self.aim, val));
+            }
+            Direction::Up(val) => {
+                let val = Some(-(val as i128));
+                self.depth = add(self.depth, val);
+                self.aim = add(self.aim, val);
+            }
+            Direction::Down(val) => {
+                let val = Some(val as i128);
+                self.depth = add(self.depth, val);
+                self.aim = add(self.aim, val);
+            }
+        }
+    }
+
+    /// Horizontal position times depth, with up and down changing the depth; `None` if
+    /// it does not fit.
+    pub fn star_one(&self) -> Option<i128> {
+        mul(self.horizontal, self.depth)
+    }
+
+    /// Horizontal position times depth, with up and down changing the aim; `None` if it
+    /// does not fit.
+    pub fn star_two(&self) -> Option<i128> {
+        mul(self.horizontal, self.aimed_depth)
+    }
+}
+
+fn follow(directions: &[Direction]) -> Position {
+    let mut pos = Position::default();
+    for dir in directions.iter() {
+        pos.apply(dir);
+    }
+    pos
+}
+
+/// Horizontal position times depth after the commands, with up and down changing the depth.
+pub fn star_one(directions: &[Direction]) -> Option<i128> {
+    follow(directions).star_one()
+}
+
+/// Horizontal position times depth after the commands, with up and down changing the aim.
+pub fn star_two(directions: &[Direction]) -> Option<i128> {
+    follow(directions).star_two()
+}
+
+/// Both stars in a single pass, without keeping the commands in memory.
+pub fn solve_stream(lines: &mut LineReader) -> Result<(Option<i128>, Option<i128>), AocError> {
+    let mut pos = Position::default();
+    while let Some((line_nr, line)) = lines.next_line() {
+        pos.apply(&parse_line(line_nr, line)?);
+    }
+
+    Ok((pos.star_one(), pos.star_two()))
+}
+
+/// The example from the puzzle description
+pub static TEST_DATA: &str = include_str!("../../examples/day02/example.txt");
+
+/// The puzzle, for the runner.
+pub struct Day02;
+
+fn answer(ans: Option<i128>) -> Answer {
+    match ans {
+        Some(ans) => Answer::Number(ans),
+        None => Answer::Unsolved(String::from("the answer does not fit in 128 bits")),
+    }
+}
+
+impl Solution for Day02 {
+    type Input = Vec<Direction>;
+
+    fn example(&self) -> &'static str {
+        TEST_DATA
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Vec<Direction>, AocError> {
+        parse(lines)
+    }
+
+    fn part_one(&self, directions: &Vec<Direction>) -> Answer {
+        answer(star_one(directions))
+    }
+
+    fn part_two(&self, directions: &Vec<Direction>) -> Answer {
+        answer(star_two(directions))
+    }
+
+    fn solve_stream(&self, lines: &mut LineReader) -> Option<Result<(Answer, Answer), AocError>> {
+        Some(solve_stream(lines).map(|(one, two)| (answer(one), answer(two))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TEST_DATA;
+
+    #[test]
+    fn test_star_one() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let ans = super::star_one(&super::parse(&lines).unwrap());
+        assert_eq!(ans, Some(150));
+    }
+
+    #[test]
+    fn test_star_two() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let ans = super::star_two(&super::parse(&lines).unwrap());
+        assert_eq!(ans, Some(900));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let lines: Vec<String> = vec![String::from("forward 5"), String::from("backward 3")];
+        let err = super::parse(&lines).unwrap_err();
+        assert_eq!(err.to_string(), "2:1: expected 'forward', 'up' or 'down', found 'backward'");
+
+        let lines: Vec<String> = vec![String::from("up x")];
+        let err = super::parse(&lines).unwrap_err();
+        assert_eq!(err.to_string(), "1:4: expected a number of at least 0, found 'x'");
+
+        let lines: Vec<String> = vec![String::from("forward -3")];
+        let err = super::parse(&lines).unwrap_err();
+        assert_eq!(err.to_string(), "1:9: expected a number of at least 0, found '-3'");
+
+        let lines: Vec<String> = vec![String::from("forward 5 6")];
+        let err = super::parse(&lines).unwrap_err();
+        assert_eq!(err.to_string(), "1:11: expected end of line, found '6'");
+
+        let lines: Vec<String> = vec![String::from("down")];
+        let err = super::parse(&lines).unwrap_err();
+        assert_eq!(err.to_string(), "1:5: expected ' ' and a number after the command");
+    }
+
+    #[test]
+    fn stream() {
+        let mut lines = common::LineReader::new(std::io::Cursor::new(TEST_DATA));
+
+        let ans = super::solve_stream(&mut lines).unwrap();
+        assert_eq!(ans, (Some(150), Some(900)));
+    }
+
+    #[test]
+    fn large_units() {
+        use common::Solution;
+
+        let max = isize::MAX;
+        let lines: Vec<String> = vec![format!("down {}", max), format!("forward {}", max)];
+        let directions = super::parse(&lines).unwrap();
+        assert_eq!(super::star_one(&directions), Some(max as i128 * max as i128));
+
+        // Part two multiplies by the aim once more, which goes past what an i128 holds
+        assert_eq!(super::star_two(&directions), None);
+        let ans = super::Day02.part_two(&directions);
+        assert_eq!(ans, common::Answer::Unsolved(String::from("the answer does not fit in 128 bits")));
+        let text = lines.join("\n");
+        let ans = super::solve_stream(&mut common::LineReader::new(std::io::Cursor::new(text))).unwrap();
+        assert_eq!(ans, (Some(max as i128 * max as i128), None));
+    }
+}
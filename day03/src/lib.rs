@@ -0,0 +1,362 @@
+//! Day 3: Binary Diagnostic. Finds the most and least common bits in the diagnostic
+//! report of the submarine.
+
+#![warn(missing_docs)]
+
+use std::vec::Vec;
+
+use common::params::{self, Param};
+use common::{Answer, AocError, LineReader, Solution};
+
+/// Gamma rate times epsilon rate: the numbers made of the most and of the least common
+/// bit at each position of the `nrbits` bit `lines`.
+pub fn star_one(lines: &[String], nrbits: usize) -> u128 {
+    let total = lines.len();
+    let mut bitcounts: Vec<usize> = vec![0; nrbits];
+
+    for line in lines.iter() {
+        for (bit, count) in bitcounts.iter_mut().enumerate() {
+            if line.chars().nth(nrbits - bit - 1)
+              .expect("Invalid line") == '1' {
+                *count += 1;
+            }
+        }
+    }
+
+    let mut gamma: usize = 0;
+    let mut epsilon: usize = 0;
+    for (bit, count) in bitcounts.iter().enumerate() {
+        if *count > (total >> 1) {
+            gamma += 1 << bit;
+        } else {
+            epsilon += 1 << bit;
+        }
+    }
+
+    gamma as u128 * epsilon as u128
+}
+
+fn find_common_bit(lines: &[&str], bit_nr: usize, most_common: bool) -> char {
+    let total = lines.len();
+    let mut count_ones = 0;
+    for line in lines.iter() {
+        if line.chars().nth(bit_nr)
+            .expect("Invalid line") == '1' {
+            count_ones += 1;
+        }
+    }
+
+    // A bit no line has cannot be kept, even if it is the least common one
+    if count_ones == 0 {
+        return '0';
+    }
+    if count_ones == total {
+        return '1';
+    }
+    if (total - count_ones) <= count_ones {
+        if most_common { '1' } else { '0' }
+    } else {
+        if most_common { '0' } else { '1' }
+    }
+}
+
+/// The line left after keeping the lines with the most or the least common bit at each
+/// position until one is left; equal lines stay together until the last bit.
+fn rating(lines: &[String], nrbits: usize, most_common: bool) -> u128 {
+    let mut lines_left: Vec<&str> = lines.iter().map(|x| x.as_str()).collect();
+    for bit in 0..nrbits {
+        if lines_left.len() == 1 {
+            break;
+        }
+        let common_bit = find_common_bit(&lines_left[..], bit, most_common);
+        lines_left.retain(|line| line.chars().nth(bit).expect("Invalid line") == common_bit);
+    }
+    u128::from_str_radix(lines_left[0], 2).expect("Invalid line")
+}
+
+/// Oxygen generator rating times CO2 scrubber rating, found by keeping the lines with
+/// the most or the least common bit at each position until one is left.
+pub fn star_two(lines: &[String], nrbits: usize) -> u128 {
+    rating(lines, nrbits, true) * rating(lines, nrbits, false)
+}
+
+/// The diagnostic report, all lines having the same number of bits
+pub struct Report {
+    /// The lines of the report, each a string of `'0'` and `'1'`
+    pub lines: Vec<String>,
+    /// Number of bits on every line
+    pub nrbits: usize,
+}
+
+fn check_line(line_nr: usize, line: &str, nrbits: usize, given: bool) -> Result<(), AocError> {
+    if let Some(pos) = line.find(|c| c != '0' && c != '1') {
+        return Err(AocError::at_first_char(line_nr, line, &line[pos..], "'0' or '1'"));
+    }
+    if line.len() != nrbits {
+        let expected = if given { format!("{} bits", nrbits) } else { format!("{} bits like the first line", nrbits) };
+        return Err(AocError::at(line_nr, line, line, &expected));
+    }
+
+    Ok(())
+}
+
+/// Most bits on a line; the gamma and epsilon rates must fit in a usize
+const MAX_BITS: usize = 63;
+
+/// Checks that every line is a binary number as long as the first, of at most 63 bits.
+pub fn parse(lines: &[String]) -> Result<Report, AocError> {
+    parse_bits(lines, None)
+}
+
+/// Like `parse`, but the lines must have `nrbits` bits if given.
+pub fn parse_bits(lines: &[String], nrbits: Option<usize>) -> Result<Report, AocError> {
+    let first = lines.first().ok_or_else(|| AocError::new(1, 1, "", "a binary number"))?;
+    let given = nrbits.is_some();
+    let nrbits = nrbits.unwrap_or(first.len());
+    if !(1..=MAX_BITS).contains(&nrbits) {
+        return Err(AocError::at(1, first, first, &format!("1 to {} bits", MAX_BITS)));
+    }
+
+    for (idx, line) in lines.iter().enumerate() {
+        check_line(idx + 1, line, nrbits, given)?;
+    }
+
+    Ok(Report {
+        lines: lines.to_vec(),
+        nrbits,
+    })
+}
+
+/// How often every value occurs in the report. Its size only depends on the number of
+/// bits, so a report of any length can be read in a single pass.
+pub struct Histogram {
+    nrbits: usize,
+    counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// An empty histogram of `nrbits` bit values.
+    pub fn new(nrbits: usize) -> Histogram {
+        Histogram { nrbits, counts: vec![0; 1 << nrbits] }
+    }
+
+    /// Counts one occurrence of `value`.
+    pub fn add(&mut self, value: usize) {
+        self.counts[value] += 1;
+    }
+
+    /// Gamma rate times epsilon rate, like `star_one`.
+    pub fn power_consumption(&self) -> usize {
+        let total: usize = self.counts.iter().sum();
+
+        let mut gamma: usize = 0;
+        let mut epsilon: usize = 0;
+        for bit in 0..self.nrbits {
+            let count: usize = self
+                .counts
+                .iter()
+                .enumerate()
+                .filter(|(value, _)| value & (1 << bit) != 0)
+                .map(|(_, count)| count)
+                .sum();
+            if count > (total >> 1) {
+                gamma += 1 << bit;
+            } else {
+                epsilon += 1 << bit;
+            }
+        }
+
+        gamma * epsilon
+    }
+
+    /// Keeps narrowing the range of values to the half with the most (or least) common
+    /// bit, like `star_two` does with the lines, until one value is left.
+    fn rating(&self, most_common: bool) -> usize {
+        let count = |lo: usize, hi: usize| -> usize { self.counts[lo..hi].iter().sum() };
+
+        let mut lo: usize = 0;
+        let mut hi: usize = 1 << self.nrbits;
+        for bit in (0..self.nrbits).rev() {
+            let mid = lo + (1 << bit);
+            let zeros = count(lo, mid);
+            let ones = count(mid, hi);
+            // A half without values cannot be kept, even if its bit is the least common one
+            let keep_ones = if zeros == 0 {
+                true
+            } else if ones == 0 {
+                false
+            } else if zeros <= ones {
+                most_common
+            } else {
+                !most_common
+            };
+            if keep_ones {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+            if count(lo, hi) == 1 {
+                break;
+            }
+        }
+
+        lo + self.counts[lo..hi].iter().position(|count| *count > 0).expect("No value left")
+    }
+
+    /// Oxygen generator rating times CO2 scrubber rating, like `star_two`.
+    pub fn life_support_rating(&self) -> usize {
+        self.rating(true) * self.rating(false)
+    }
+}
+
+/// Widest report `solve_stream` accepts, the histogram has an entry for every value
+const MAX_STREAM_BITS: usize = 20;
+
+/// Both stars in a single pass over the report, see `Histogram`. The lines must have
+/// `nrbits` bits if given, otherwise as many as the first line.
+pub fn solve_stream(lines: &mut LineReader, given_bits: Option<usize>) -> Result<(usize, usize), AocError> {
+    let mut histogram: Option<Histogram> = None;
+    while let Some((line_nr, line)) = lines.next_line() {
+        let nrbits = match &histogram {
+            Some(histogram) => histogram.nrbits,
+            None => given_bits.unwrap_or(line.len()),
+        };
+        if nrbits > MAX_STREAM_BITS {
+            return Err(AocError::at(line_nr, line, line, &format!("at most {} bits", MAX_STREAM_BITS)));
+        }
+        check_line(line_nr, line, nrbits, given_bits.is_some())?;
+        let value = usize::from_str_radix(line, 2).map_err(|_| AocError::at(line_nr, line, line, "a binary number"))?;
+        histogram.get_or_insert_with(|| Histogram::new(nrbits)).add(value);
+    }
+
+    let histogram = histogram.ok_or_else(|| AocError::new(1, 1, "", "a binary number"))?;
+    Ok((histogram.power_consumption(), histogram.life_support_rating()))
+}
+
+/// The example from the puzzle description
+pub static TEST_DATA: &str = include_str!("../../examples/day03/example.txt");
+
+/// The puzzle, for the runner, with the number of bits on every line.
+#[derive(Default)]
+pub struct Day03 {
+    /// Bits on every line, or as many as on the first line if not given
+    pub bits: Option<usize>,
+}
+
+static PARAMS: &[Param] = &[Param { name: "bits", default: "auto", help: "bits on every line, auto to use the first line" }];
+
+impl Solution for Day03 {
+    type Input = Report;
+
+    fn example(&self) -> &'static str {
+        TEST_DATA
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Report, AocError> {
+        parse_bits(lines, self.bits)
+    }
+
+    // Products of two numbers of at most 63 bits fit in an i128
+    fn part_one(&self, report: &Report) -> Answer {
+        Answer::Number(star_one(&report.lines, report.nrbits) as i128)
+    }
+
+    fn part_two(&self, report: &Report) -> Answer {
+        Answer::Number(star_two(&report.lines, report.nrbits) as i128)
+    }
+
+    fn solve_stream(&self, lines: &mut LineReader) -> Option<Result<(Answer, Answer), AocError>> {
+        Some(solve_stream(lines, self.bits).map(|(one, two)| (one.into(), two.into())))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "bits" if value == "auto" => self.bits = None,
+            "bits" => self.bits = Some(params::number(name, value, 1..=MAX_BITS)?),
+            _ => return Err(format!("unknown parameter '{}'", name)),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TEST_DATA;
+
+    #[test]
+    fn test_star_one() {
+        let lines: Vec<String> = TEST_DATA
+            .lines()
+            .map(|x| x.to_string())
+            .collect();
+
+        let ans = super::star_one(&lines, 5);
+        assert_eq!(ans, 198);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let lines: Vec<String> = TEST_DATA
+            .lines()
+            .map(|x| x.to_string())
+            .collect();
+
+        let ans = super::star_two(&lines, 5);
+        assert_eq!(ans, 230);
+    }
+
+    #[test]
+    fn same_bit_everywhere() {
+        let lines: Vec<String> = ["10", "11"].iter().map(|x| x.to_string()).collect();
+        assert_eq!(super::star_two(&lines, 2), 3 * 2);
+
+        let lines: Vec<String> = ["01", "01"].iter().map(|x| x.to_string()).collect();
+        assert_eq!(super::star_two(&lines, 2), 1);
+
+        let mut lines = common::LineReader::new(std::io::Cursor::new("10\n11\n"));
+        assert_eq!(super::solve_stream(&mut lines, None).unwrap(), (2, 3 * 2));
+        let mut lines = common::LineReader::new(std::io::Cursor::new("01\n01\n"));
+        assert_eq!(super::solve_stream(&mut lines, None).unwrap(), (2, 1));
+    }
+
+    #[test]
+    fn stream() {
+        let mut lines = common::LineReader::new(std::io::Cursor::new(TEST_DATA));
+
+        let ans = super::solve_stream(&mut lines, None).unwrap();
+        assert_eq!(ans, (198, 230));
+    }
+
+    #[test]
+    fn bits() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        assert_eq!(super::parse_bits(&lines, Some(5)).unwrap().nrbits, 5);
+        let err = super::parse_bits(&lines, Some(12)).err().unwrap();
+        assert_eq!(err.expected, "12 bits");
+        let mut lines = common::LineReader::new(std::io::Cursor::new(TEST_DATA));
+        assert!(super::solve_stream(&mut lines, Some(4)).is_err());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let lines = |text: &str| -> Vec<String> { text.lines().map(|x| x.to_string()).collect() };
+        let err = super::parse(&[]).err().unwrap();
+        assert_eq!(err.to_string(), "1:1: expected a binary number");
+        let err = super::parse(&lines("\n")).err().unwrap();
+        assert_eq!(err.to_string(), "1:1: expected 1 to 63 bits");
+        let err = super::parse(&lines(&"1".repeat(70))).err().unwrap();
+        assert_eq!(err.expected, "1 to 63 bits");
+
+        // The widest lines and equal lines can be solved
+        let report = super::parse(&lines(&format!("{}\n{}\n", "1".repeat(63), "0".repeat(63)))).unwrap();
+        assert_eq!(super::star_one(&report.lines, report.nrbits), 0);
+        assert_eq!(super::star_two(&report.lines, report.nrbits), 0);
+        let report = super::parse(&lines("110\n110\n011")).unwrap();
+        assert_eq!(super::star_two(&report.lines, report.nrbits), 6 * 3);
+    }
+}
@@ -0,0 +1,277 @@
+//! Day 4: Giant Squid. Plays bingo against a giant squid, finding the boards that
+//! win first and last.
+
+#![warn(missing_docs)]
+
+use std::fmt;
+use std::vec::Vec;
+
+use common::parse::{self, Section};
+use common::rng::Rng;
+use common::{Answer, AocError, Solution};
+
+#[derive(Debug, Clone)]
+struct Number {
+    nr: usize,
+    marked: bool,
+}
+
+/// A 5x5 bingo board and the numbers marked on it.
+#[derive(Debug, Clone)]
+pub struct Board {
+    nrs: Vec<Vec<Number>>,
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in &self.nrs {
+            for x in y {
+                write!(f, "{num:>2} ", num = x.nr)?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(f)
+    }
+}
+
+impl Board {
+    /// Marks `num` if it is on the board.
+    pub fn mark(&mut self, num: usize) {
+        for y in &mut self.nrs {
+            for x in &mut (*y) {
+                if x.nr == num {
+                    x.marked = true;
+                }
+            }
+        }
+    }
+
+    /// Whether a complete row or column is marked.
+    pub fn has_won(&self) -> bool {
+        // Lines
+        for y in &self.nrs {
+            if y.iter().fold(true, |acc, num| acc & num.marked) {
+                return true;
+            }
+        }
+
+        // Columns
+        for x in 0..self.nrs[0].len() {
+            if (0..self.nrs.len()).fold(true, |acc, y| acc & self.nrs[y][x].marked) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// The sum of the unmarked numbers times `last_ans`, the number that was just drawn.
+    pub fn calc_score(&self, last_ans: usize) -> usize {
+        last_ans
+            * self.nrs.iter().fold(0, |yacc, xvec| {
+                yacc + xvec
+                    .iter()
+                    .fold(0, |xacc, num| if num.marked { xacc } else { xacc + num.nr })
+            })
+    }
+}
+
+/// Largest number on a board; the score of larger ones could overflow.
+const MAX_BOARD_NUMBER: usize = 1_000_000;
+
+fn parse_board(section: &Section) -> Result<Board, AocError> {
+    let what = format!("a board number up to {}", MAX_BOARD_NUMBER);
+    let mut board = Board { nrs: Vec::new() };
+    for (line_nr, line) in section.numbered() {
+        if board.nrs.len() == 5 {
+            return Err(AocError::at(line_nr, line, line, "an empty line after 5 board rows"));
+        }
+        let row = line
+            .split_whitespace()
+            .map(|num| {
+                match parse::number(line_nr, line, num, &what)? {
+                    nr if nr <= MAX_BOARD_NUMBER => Ok(Number { nr, marked: false }),
+                    _ => Err(AocError::at(line_nr, line, num, &what)),
+                }
+            })
+            .collect::<Result<Vec<Number>, AocError>>()?;
+        if row.len() != 5 {
+            return Err(AocError::at(line_nr, line, line, "a board row of 5 numbers"));
+        }
+        board.nrs.push(row);
+    }
+    if board.nrs.len() < 5 {
+        return Err(AocError::new(section.end_line(), 1, "", "a board row of 5 numbers"));
+    }
+
+    Ok(board)
+}
+
+/// The drawn numbers and the boards they are marked on
+#[derive(Debug)]
+pub struct Bingo {
+    answers: Vec<usize>,
+    boards: Vec<Board>,
+}
+
+impl Bingo {
+    /// The numbers in the order they are drawn.
+    pub fn draws(&self) -> &[usize] {
+        &self.answers
+    }
+
+    /// The boards, with nothing marked yet.
+    pub fn boards(&self) -> &[Board] {
+        &self.boards
+    }
+}
+
+/// A line of drawn numbers followed by boards, all separated by empty lines.
+pub fn parse(lines: &[String]) -> Result<Bingo, AocError> {
+    let mut sections = parse::sections(lines);
+    let draws = sections.expect("a line of drawn numbers")?;
+    let (line_nr, line) = (draws.first_line, &draws.lines[0]);
+    if let Some((line_nr, line)) = draws.numbered().nth(1) {
+        return Err(AocError::at(line_nr, line, line, "an empty line after the drawn numbers"));
+    }
+    let answers: Vec<usize> = parse::comma_separated(line_nr, line, "a drawn number")?;
+
+    let boards = sections.map(|section| parse_board(&section)).collect::<Result<Vec<Board>, AocError>>()?;
+    if boards.is_empty() {
+        return Err(AocError::new(lines.len() + 1, 1, "", "a bingo board"));
+    }
+
+    Ok(Bingo { answers, boards })
+}
+
+/// Score of the first board to win, or 0 if none does.
+pub fn star_one(bingo: &Bingo) -> usize {
+    let mut boards = bingo.boards.clone();
+
+    for &ans in &bingo.answers {
+        for board in boards.iter_mut() {
+            board.mark(ans);
+            if board.has_won() {
+                return board.calc_score(ans);
+            }
+        }
+    }
+
+    0
+}
+
+/// Score of the last board to win, or 0 if not all boards win.
+pub fn star_two(bingo: &Bingo) -> usize {
+    let mut boards = bingo.boards.clone();
+
+    let board_total = boards.len();
+    let mut board_count: usize = 0;
+    for &ans in &bingo.answers {
+        for board in boards.iter_mut() {
+            if !board.has_won() {
+                board.mark(ans);
+                if board.has_won() {
+                    board_count += 1;
+                    if board_count == board_total {
+                        return board.calc_score(ans);
+                    }
+                }
+            }
+        }
+    }
+
+    0
+}
+
+/// Numbers are drawn from `0..NUMBERS`, like in the real inputs.
+const NUMBERS: usize = 100;
+
+/// A game with all numbers drawn once and `size` boards (at least one).
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut draws: Vec<usize> = (0..NUMBERS).collect();
+    rng.shuffle(&mut draws);
+    let mut out = draws.iter().map(|nr| nr.to_string()).collect::<Vec<String>>().join(",");
+    out.push('\n');
+
+    let mut numbers: Vec<usize> = (0..NUMBERS).collect();
+    for _ in 0..size.max(1) {
+        rng.shuffle(&mut numbers);
+        out.push('\n');
+        for row in numbers[..25].chunks(5) {
+            let row: Vec<String> = row.iter().map(|nr| format!("{:>2}", nr)).collect();
+            out.push_str(&row.join(" "));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// The example from the puzzle description
+pub static TEST_DATA: &str = include_str!("../../examples/day04/example.txt");
+
+/// The puzzle, for the runner.
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Bingo;
+
+    fn example(&self) -> &'static str {
+        TEST_DATA
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Bingo, AocError> {
+        parse(lines)
+    }
+
+    fn part_one(&self, bingo: &Bingo) -> Answer {
+        star_one(bingo).into()
+    }
+
+    fn part_two(&self, bingo: &Bingo) -> Answer {
+        star_two(bingo).into()
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TEST_DATA;
+
+    #[test]
+    fn test_star_one() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let ans = super::star_one(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 4512);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let ans = super::star_two(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 1924);
+    }
+
+    #[test]
+    fn generate() {
+        let input = super::generate(&mut common::rng::Rng::new(4), 30);
+        assert_eq!(input, super::generate(&mut common::rng::Rng::new(4), 30));
+
+        let lines: Vec<String> = input.lines().map(|x| x.to_string()).collect();
+        let bingo = super::parse(&lines).unwrap();
+        assert_eq!(bingo.answers.len(), super::NUMBERS);
+        assert_eq!(bingo.boards.len(), 30);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let mut lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+        lines[2] = lines[2].replacen("22", "18446744073709551615", 1);
+        let err = super::parse(&lines).unwrap_err();
+        assert_eq!(err.to_string(), "3:1: expected a board number up to 1000000, found '18446744073709551615'");
+    }
+}
@@ -0,0 +1,304 @@
+//! Day 5: Hydrothermal Venture. Counts the points where lines of hydrothermal vents
+//! overlap, first ignoring the diagonal lines.
+
+#![warn(missing_docs)]
+
+use std::vec::Vec;
+use std::fmt;
+
+use common::render::{self, Animation};
+use common::rng::Rng;
+use common::{parse, Answer, AocError, Grid, LineReader, Solution};
+
+#[derive(Debug)]
+struct Field {
+    pos: Grid<usize>,
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cells = self.pos.map(|x| if *x > 0 { x.to_string() } else { String::from(".") });
+        writeln!(f, "{}", cells)
+    }
+}
+
+impl Field {
+    fn walk(&mut self, startx: usize, starty: usize, endx: usize, endy: usize) {
+        // Extend field if necessary
+        let width = self.pos.width().max(startx + 1).max(endx + 1);
+        let height = self.pos.height().max(starty + 1).max(endy + 1);
+        self.pos.resize(width, height, 0);
+
+        // Do the walk
+        let mut y = starty;
+        let mut x = startx;
+        while y != endy || x != endx {
+            self.pos[(x, y)] += 1;
+            if y != endy {
+                if y > endy {
+                    y -= 1;
+                } else {
+                    y += 1;
+                }
+            }
+            if x != endx {
+                if x > endx {
+                    x -=1;
+                } else {
+                    x += 1;
+                }
+            }
+        }
+        self.pos[(x, y)] += 1;
+    }
+
+    fn count_bigger(&self, target: usize) -> usize {
+        self.pos.iter().filter(|nr| **nr >= target).count()
+    }
+}
+
+/// A line of vents from (startx, starty) to (endx, endy), both ends inclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    /// X coordinate of the start
+    pub startx: usize,
+    /// Y coordinate of the start
+    pub starty: usize,
+    /// X coordinate of the end
+    pub endx: usize,
+    /// Y coordinate of the end
+    pub endy: usize,
+}
+
+/// Largest width and height of the field that is accepted; the field is stored as a
+/// grid, so a single far away vent would otherwise take all memory.
+const MAX_FIELD_SIZE: usize = 4096;
+
+fn parse_line(line_nr: usize, line: &str) -> Result<Segment, AocError> {
+    let (start, end) = parse::arrow(line_nr, line, "' -> ' between start and end")?;
+    let coordinate = |text: &str| match parse::coordinate(line_nr, line, text)? {
+        (x, y) if x < MAX_FIELD_SIZE && y < MAX_FIELD_SIZE => Ok((x, y)),
+        _ => Err(AocError::at(line_nr, line, text, &format!("coordinates below {}", MAX_FIELD_SIZE))),
+    };
+    let (startx, starty) = coordinate(start)?;
+    let (endx, endy) = coordinate(end)?;
+
+    Ok(Segment { startx, starty, endx, endy })
+}
+
+/// The segments, one `x1,y1 -> x2,y2` per line.
+pub fn parse(lines: &[String]) -> Result<Vec<Segment>, AocError> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| parse_line(idx + 1, line))
+        .collect()
+}
+
+impl Segment {
+    /// Whether the segment is horizontal or vertical.
+    pub fn is_straight(&self) -> bool {
+        self.startx == self.endx || self.starty == self.endy
+    }
+}
+
+fn build_field(segments: &[Segment], count_diagonal: bool) -> Field {
+    let mut field = Field { pos: Grid::new(0, 0, 0) };
+
+    for s in segments {
+        if count_diagonal || s.is_straight() {
+            field.walk(s.startx, s.starty, s.endx, s.endy)
+        }
+    }
+
+    field
+}
+
+/// Number of points where horizontal and vertical segments overlap.
+pub fn star_one(segments: &[Segment]) -> usize {
+    let field = build_field(segments, false);
+    field.count_bigger(2)
+}
+
+/// Number of points where any segments overlap.
+pub fn star_two(segments: &[Segment]) -> usize {
+    let field = build_field(segments, true);
+    field.count_bigger(2)
+}
+
+/// Both stars in a single pass; only the fields are kept in memory, whose size depends
+/// on the coordinates and not on the number of lines.
+pub fn solve_stream(lines: &mut LineReader) -> Result<(usize, usize), AocError> {
+    let mut straight = Field { pos: Grid::new(0, 0, 0) };
+    let mut all = Field { pos: Grid::new(0, 0, 0) };
+    while let Some((line_nr, line)) = lines.next_line() {
+        let s = parse_line(line_nr, line)?;
+        if s.is_straight() {
+            straight.walk(s.startx, s.starty, s.endx, s.endy);
+        }
+        all.walk(s.startx, s.starty, s.endx, s.endy);
+    }
+
+    Ok((straight.count_bigger(2), all.count_bigger(2)))
+}
+
+/// Colour of each number of overlapping vents, the last one also for more overlaps
+fn palette() -> Vec<render::Rgb> {
+    let mut palette = vec![[0, 0, 0]];
+    palette.extend(render::gradient([0, 64, 160], [255, 220, 0], 5));
+    palette
+}
+
+/// The field with only the horizontal and vertical lines, then with all lines.
+pub fn render(segments: &[Segment]) -> Animation {
+    let palette = palette();
+    let max = palette.len() - 1;
+    let mut anim = Animation::new(palette).with_delay(100);
+    for count_diagonal in [false, true] {
+        let field = build_field(segments, count_diagonal);
+        anim.push_frame(field.pos.map(|count| (*count).min(max) as u8));
+    }
+    anim
+}
+
+/// Coordinates are below `FIELD_SIZE`, like in the real inputs.
+const FIELD_SIZE: usize = 1000;
+
+/// `size` lines of vents (at least one), each horizontal, vertical or at 45 degrees.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size.max(1) {
+        let (startx, starty) = (rng.range(0..FIELD_SIZE), rng.range(0..FIELD_SIZE));
+        let (mut endx, mut endy) = (rng.range(0..FIELD_SIZE), rng.range(0..FIELD_SIZE));
+        match rng.range(0..3) {
+            0 => endy = starty,
+            1 => endx = startx,
+            _ => {
+                // Same distance along both axes, in the direction of the random end
+                let len = startx.abs_diff(endx).min(starty.abs_diff(endy));
+                endx = if endx < startx { startx - len } else { startx + len };
+                endy = if endy < starty { starty - len } else { starty + len };
+            }
+        }
+        out.push_str(&format!("{},{} -> {},{}\n", startx, starty, endx, endy));
+    }
+    out
+}
+
+/// The example from the puzzle description
+pub static TEST_DATA: &str = include_str!("../../examples/day05/example.txt");
+
+/// The puzzle, for the runner.
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Vec<Segment>;
+
+    fn example(&self) -> &'static str {
+        TEST_DATA
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Vec<Segment>, AocError> {
+        parse(lines)
+    }
+
+    fn part_one(&self, segments: &Vec<Segment>) -> Answer {
+        star_one(segments).into()
+    }
+
+    fn part_two(&self, segments: &Vec<Segment>) -> Answer {
+        star_two(segments).into()
+    }
+
+    fn solve_stream(&self, lines: &mut LineReader) -> Option<Result<(Answer, Answer), AocError>> {
+        Some(solve_stream(lines).map(|(one, two)| (one.into(), two.into())))
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn render(&self, segments: &Vec<Segment>) -> Option<Animation> {
+        Some(render(segments))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TEST_DATA;
+
+    #[test]
+    fn test_star_one() {
+        let lines: Vec<String> = TEST_DATA
+            .lines()
+            .map(|x| x.to_string())
+            .collect();
+
+        let ans = super::star_one(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 5);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let lines: Vec<String> = TEST_DATA
+            .lines()
+            .map(|x| x.to_string())
+            .collect();
+
+        let ans = super::star_two(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 12);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let lines: Vec<String> = vec![String::from("0,9 -> 5,9"), String::from("8,0 -> 0;8")];
+        let err = super::parse(&lines).unwrap_err();
+        assert_eq!(err.to_string(), "2:9: expected ',' after X coordinate, found ';'");
+
+        let lines: Vec<String> = vec![String::from("0,9 => 5,9")];
+        let err = super::parse(&lines).unwrap_err();
+        assert_eq!(err.to_string(), "1:4: expected ' -> ' between start and end, found ' => 5,9'");
+
+        let lines: Vec<String> = vec![String::from("0,a -> 5,9")];
+        let err = super::parse(&lines).unwrap_err();
+        assert_eq!(err.to_string(), "1:3: expected Y coordinate, found 'a'");
+
+        let lines: Vec<String> = vec![String::from("0,9 -> 4096,9")];
+        let err = super::parse(&lines).unwrap_err();
+        assert_eq!(err.to_string(), "1:8: expected coordinates below 4096, found '4096,9'");
+    }
+
+    #[test]
+    fn stream() {
+        let mut lines = common::LineReader::new(std::io::Cursor::new(TEST_DATA));
+
+        let ans = super::solve_stream(&mut lines).unwrap();
+        assert_eq!(ans, (5, 12));
+    }
+
+    #[test]
+    fn render() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let anim = super::render(&super::parse(&lines).unwrap());
+        let overlaps: Vec<usize> = anim.frames().iter().map(|f| f.iter().filter(|c| **c >= 2).count()).collect();
+        assert_eq!(overlaps, vec![5, 12]);
+    }
+
+    #[test]
+    fn generate() {
+        let input = super::generate(&mut common::rng::Rng::new(5), 200);
+        assert_eq!(input, super::generate(&mut common::rng::Rng::new(5), 200));
+
+        let lines: Vec<String> = input.lines().map(|x| x.to_string()).collect();
+        let segments = super::parse(&lines).unwrap();
+        assert_eq!(segments.len(), 200);
+        for s in &segments {
+            assert!(s.is_straight() || s.startx.abs_diff(s.endx) == s.starty.abs_diff(s.endy), "{:?}", s);
+        }
+
+        let mut stream = common::LineReader::new(std::io::Cursor::new(input));
+        let ans = super::solve_stream(&mut stream).unwrap();
+        assert_eq!(ans, (super::star_one(&segments), super::star_two(&segments)));
+    }
+}
@@ -0,0 +1,270 @@
+//! Day 6: Lanternfish. Counts a school of lanternfish that grows exponentially, each
+//! fish spawning a new one every seven days.
+
+#![warn(missing_docs)]
+
+use std::vec::Vec;
+
+use common::bigint::BigUint;
+use common::params::{self, Param};
+use common::visual::{self, Frame};
+use common::{parse, Answer, AocError, Solution};
+
+/// A single fish and the days until it spawns a new one.
+#[derive(Debug)]
+pub struct LanternFish {
+    age: usize,
+}
+
+impl LanternFish {
+    /// A fish that spawns after `age` more days.
+    pub fn new(age: usize) -> LanternFish {
+        LanternFish { age }
+    }
+
+    /// Ages the fish by a day; returns whether it spawns a new fish.
+    pub fn day_passes(&mut self) -> bool {
+        if self.age == 0 {
+            self.age = 6;
+            return true;
+        } else {
+            self.age -= 1;
+        }
+
+        false
+    }
+}
+
+/// The ages of the fish, on one comma separated line.
+pub fn parse(lines: &[String]) -> Result<Vec<usize>, AocError> {
+    let line = parse::single_line(lines, "a line of comma separated numbers")?;
+    line.split(',')
+        .map(|x| match parse::number::<usize>(1, line, x, "a fish age from 0 to 8") {
+            Ok(age) if age <= 8 => Ok(age),
+            _ => Err(AocError::at(1, line, x, "a fish age from 0 to 8")),
+        })
+        .collect()
+}
+
+/// Number of fish after `days` days, simulating every fish on its own; fine for a few
+/// days, but the number of fish grows exponentially. See `count_fish` for any number
+/// of days.
+pub fn simulate(ages: &[usize], days: usize) -> usize {
+    let mut fishes: Vec<LanternFish> = Vec::new();
+    for &age in ages {
+        fishes.push(LanternFish::new(age));
+    }
+
+    for _ in 0..days {
+        let mut new_fishes: Vec<LanternFish> = Vec::new();
+        for fish in &mut fishes {
+            if fish.day_passes() {
+                new_fishes.push(LanternFish::new(8));
+            }
+        }
+        fishes.append(&mut new_fishes);
+    }
+
+    fishes.len()
+}
+
+/// Number of fish of each age. The counts outgrow any fixed size integer after a few
+/// hundred days.
+type AgeMap = [BigUint; 9];
+
+fn age_map(ages: &[usize]) -> AgeMap {
+    let mut counts = [0usize; 9];
+    for &age in ages {
+        counts[age] += 1;
+    }
+    counts.map(BigUint::from)
+}
+
+fn pass_day(age_map: &mut AgeMap) {
+    // The fish at 0 move to 8 as their newborns, and spawn again at 6
+    age_map.rotate_left(1);
+    let zeroes = age_map[8].clone();
+    age_map[6] += &zeroes;
+}
+
+/// Number of fish after `days` days, starting with fish of the given ages. Only keeps
+/// the number of fish of each age, so the work does not depend on how many fish there are.
+pub fn count_fish(ages: &[usize], days: usize) -> BigUint {
+    let mut age_map = age_map(ages);
+    for _ in 0..days {
+        pass_day(&mut age_map);
+    }
+
+    age_map.iter().sum()
+}
+
+/// Number of fish after 80 days.
+pub fn star_one(ages: &[usize]) -> BigUint {
+    count_fish(ages, 80)
+}
+
+/// Number of fish after 256 days.
+pub fn star_two(ages: &[usize]) -> BigUint {
+    count_fish(ages, 256)
+}
+
+/// The number of fish of each age for every day up to `days`, as bars relative to the
+/// largest group; fish about to spawn are red and newborn fish green.
+pub fn visualize(ages: &[usize], days: usize) -> Vec<Frame> {
+    let mut age_map = age_map(ages);
+    let mut frames = Vec::new();
+    for day in 0..=days {
+        let max = age_map.iter().max().unwrap();
+        let text: String = age_map
+            .iter()
+            .enumerate()
+            .map(|(age, count)| {
+                let colour = match age {
+                    0 => 196,
+                    7 | 8 => 40,
+                    _ => 33,
+                };
+                visual::big_bar(&format!("age {}", age), count, max, 60, colour)
+            })
+            .collect();
+        frames.push(Frame::new(format!("Day {}: {} fish", day, age_map.iter().sum::<BigUint>()), text));
+        pass_day(&mut age_map);
+    }
+    frames
+}
+
+/// The example from the puzzle description
+pub static TEST_DATA: &str = include_str!("../../examples/day06/example.txt");
+
+/// The puzzle, for the runner, with the number of days to simulate.
+pub struct Day06 {
+    /// Days simulated for part one
+    pub days_one: usize,
+    /// Days simulated for part two
+    pub days_two: usize,
+}
+
+impl Default for Day06 {
+    fn default() -> Day06 {
+        Day06 { days_one: 80, days_two: 256 }
+    }
+}
+
+static PARAMS: &[Param] = &[
+    Param { name: "days_one", default: "80", help: "days to simulate for part one" },
+    Param { name: "days_two", default: "256", help: "days to simulate for part two" },
+    Param { name: "days", default: "80,256", help: "days to simulate for part one and two, or one number for both" },
+];
+
+/// Most days that can be simulated.
+const MAX_DAYS: usize = 100_000;
+
+impl Solution for Day06 {
+    type Input = Vec<usize>;
+
+    fn example(&self) -> &'static str {
+        TEST_DATA
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Vec<usize>, AocError> {
+        parse(lines)
+    }
+
+    fn part_one(&self, ages: &Vec<usize>) -> Answer {
+        count_fish(ages, self.days_one).into()
+    }
+
+    fn part_two(&self, ages: &Vec<usize>) -> Answer {
+        count_fish(ages, self.days_two).into()
+    }
+
+    fn visualize(&self, ages: &Vec<usize>) -> Option<Vec<Frame>> {
+        Some(visualize(ages, self.days_two))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "days_one" => self.days_one = params::number(name, value, 0..=MAX_DAYS)?,
+            "days_two" => self.days_two = params::number(name, value, 0..=MAX_DAYS)?,
+            "days" => (self.days_one, self.days_two) = params::per_part(name, value, 0..=MAX_DAYS)?,
+            _ => return Err(format!("unknown parameter '{}'", name)),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::bigint::BigUint;
+
+    use super::TEST_DATA;
+
+    #[test]
+    fn test_star_one() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let ans = super::star_one(&super::parse(&lines).unwrap());
+        assert_eq!(ans, BigUint::from(5934u64));
+    }
+
+    #[test]
+    fn test_star_two() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let ans = super::star_two(&super::parse(&lines).unwrap());
+        assert_eq!(ans, BigUint::from(26984457539u64));
+    }
+
+    #[test]
+    fn visualize() {
+        let frames = super::visualize(&[3, 4, 3, 1, 2], 256);
+        assert_eq!(frames.len(), 257);
+        assert_eq!(frames[18].caption, "Day 18: 26 fish");
+        assert_eq!(frames[80].caption, "Day 80: 5934 fish");
+        assert_eq!(frames[0].text.lines().count(), 9);
+    }
+
+    #[test]
+    fn params() {
+        use common::{Puzzle, Solution};
+
+        let mut day = super::Day06::default();
+        day.set_param("days_two", "18").unwrap();
+        assert!(day.set_param("days_one", "-5").is_err());
+        assert!(day.set_param("weeks", "5").is_err());
+        let ages = day.parse_input(&[String::from("3,4,3,1,2")]).unwrap();
+        assert_eq!(day.solve(&*ages, common::Part::One), common::Answer::Number(5934));
+        assert_eq!(day.solve(&*ages, common::Part::Two), common::Answer::Number(26));
+        assert_eq!(day.params().len(), 3);
+
+        // Every default is a valid value
+        for param in day.params() {
+            day.set_param(param.name, param.default).unwrap();
+        }
+        assert_eq!((day.days_one, day.days_two), (80, 256));
+
+        // Far past where any fixed size integer overflows
+        day.set_param("days", "5000").unwrap();
+        let ans = day.solve(&*ages, common::Part::Two).to_string();
+        assert_eq!(ans.len(), 190);
+        assert!(ans.starts_with("82656898780567729956") && ans.ends_with("86246752682993331996"), "{}", ans);
+    }
+
+    #[test]
+    fn simulate_matches_count() {
+        common::prop::check(
+            300,
+            |rng, size| {
+                let ages: Vec<usize> = (0..rng.range(0..size / 5 + 2)).map(|_| rng.range(0..9)).collect();
+                (ages, rng.range(0..size / 2 + 10))
+            },
+            |(ages, days)| {
+                common::prop::equal(BigUint::from(super::simulate(ages, *days)), super::count_fish(ages, *days), "fish counts")
+            },
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Day 7: The Treachery of Whales. Finds the position the crabs can line up on with
+//! the least fuel.
+
+#![warn(missing_docs)]
+
+use std::vec::Vec;
+
+use common::{parse, Answer, AocError, Solution};
+
+/// Crabs further out take too long to try every position in between.
+const MAX_POSITION: i32 = 100_000;
+
+/// The horizontal positions of the crabs, on one comma separated line.
+pub fn parse(lines: &[String]) -> Result<Vec<i32>, AocError> {
+    let line = parse::single_line(lines, "a line of comma separated numbers")?;
+    let what = format!("a crab position from 0 to {}", MAX_POSITION);
+    line.split(',')
+        .map(|x| match parse::number::<i32>(1, line, x, &what) {
+            Ok(pos) if (0..=MAX_POSITION).contains(&pos) => Ok(pos),
+            _ => Err(AocError::at(1, line, x, &what)),
+        })
+        .collect()
+}
+
+/// Least fuel to line up on any position, when moving a distance takes `fuel(distance)`.
+fn least_fuel(crabs: &[i32], fuel: impl Fn(i64) -> i64) -> i64 {
+    let &max = crabs.iter().max().unwrap();
+    let &min = crabs.iter().min().unwrap();
+
+    (min..(max + 1))
+        .map(|pos| crabs.iter().map(|x| fuel(i64::from(i32::abs(pos - x)))).sum())
+        .min()
+        .unwrap()
+}
+
+/// Least fuel to line up when each step costs one fuel.
+pub fn star_one(crabs: &[i32]) -> i64 {
+    least_fuel(crabs, |distance| distance)
+}
+
+/// Least fuel to line up when each step costs one more fuel than the step before.
+pub fn star_two(crabs: &[i32]) -> i64 {
+    least_fuel(crabs, |distance| distance * (distance + 1) / 2)
+}
+
+/// The example from the puzzle description
+pub static TEST_DATA: &str = include_str!("../../examples/day07/example.txt");
+
+/// The puzzle, for the runner.
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<i32>;
+
+    fn example(&self) -> &'static str {
+        TEST_DATA
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Vec<i32>, AocError> {
+        parse(lines)
+    }
+
+    fn part_one(&self, crabs: &Vec<i32>) -> Answer {
+        star_one(crabs).into()
+    }
+
+    fn part_two(&self, crabs: &Vec<i32>) -> Answer {
+        star_two(crabs).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TEST_DATA;
+
+    #[test]
+    fn test_star_one() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let ans = super::star_one(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 37);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let ans = super::star_two(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 168);
+    }
+
+    #[test]
+    fn lined_up() {
+        let crabs = super::parse(&[String::from("5,5,5")]).unwrap();
+        assert_eq!(super::star_one(&crabs), 0);
+        assert_eq!(super::star_two(&crabs), 0);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = super::parse(&[String::from("1,-2")]).unwrap_err();
+        assert_eq!(err.to_string(), "1:3: expected a crab position from 0 to 100000, found '-2'");
+
+        let err = super::parse(&[String::from("100001")]).unwrap_err();
+        assert_eq!(err.to_string(), "1:1: expected a crab position from 0 to 100000, found '100001'");
+    }
+}
@@ -0,0 +1,242 @@
+//! Day 8: Seven Segment Search. Works out which wires drive which segments of the
+//! scrambled seven-segment displays, and what they show.
+
+#![warn(missing_docs)]
+
+use std::collections::HashSet;
+use std::vec::Vec;
+
+use common::{Answer, AocError, Solution};
+
+/// The number on a display, decoded from its ten signal patterns `alldigits` and the
+/// four patterns it shows, `currentdisplay`. Both are separated by spaces. `None` if
+/// the patterns are not those of the ten digits or the display shows something else.
+pub fn parse_display(alldigits: &str, currentdisplay: &str) -> Option<usize> {
+    read_display(&wiring(alldigits)?, currentdisplay)
+}
+
+/// The wires of each digit, from the ten signal patterns `alldigits`.
+fn wiring(alldigits: &str) -> Option<Vec<HashSet<char>>> {
+    // Thanks to Bras (https://github.com/MBras) for the idea of the solution, comparing differences in wires
+
+    let digit_strings: Vec<&str> = alldigits.split(' ').collect();
+
+    let mut digits: Vec<HashSet<char>> = vec![HashSet::new(); 10];
+
+    // Find the '1'
+    digits[1] = HashSet::from_iter(digit_strings
+        .iter()
+        .find(|s| s.len() == 2)?
+        .chars());
+
+    // Find the '7'
+    digits[7] = HashSet::from_iter(digit_strings
+        .iter()
+        .find(|s| s.len() == 3)?
+        .chars());
+
+    // Find the '4'
+    digits[4] = HashSet::from_iter(digit_strings
+        .iter()
+        .find(|s| s.len() == 4)?
+        .chars());
+
+    // Find the '8'
+    digits[8] = HashSet::from_iter(digit_strings
+        .iter()
+        .find(|s| s.len() == 7)?
+        .chars());
+
+    // Find the '3'
+    // The set difference with the '7' should be 2 wires
+    digits[3] = HashSet::from_iter(digit_strings
+        .iter()
+        .find(|s| s.len() == 5
+            && HashSet::from_iter(s.chars()).difference(&digits[7]).count() == 2)?
+        .chars());
+
+    // Find the '6'
+    // The set difference with the '7' should be 4 wires
+    digits[6] = HashSet::from_iter(digit_strings
+        .iter()
+        .find(|s| s.len() == 6
+            && HashSet::from_iter(s.chars()).difference(&digits[7]).count() == 4)?
+        .chars());
+
+    // Find the '0'
+    // The set difference with the '3' should be 2 wires
+    digits[0] = HashSet::from_iter(digit_strings
+        .iter()
+        .find(|s| s.len() == 6
+            && !HashSet::from_iter(s.chars()).eq(&digits[6])
+            && HashSet::from_iter(s.chars()).difference(&digits[3]).count() == 2)?
+        .chars());
+
+    // Find the '9'
+    // The last one remaining with 6 wires
+    digits[9] = HashSet::from_iter(digit_strings
+        .iter()
+        .find(|s| s.len() == 6
+            && !HashSet::from_iter(s.chars()).eq(&digits[6])
+            && !HashSet::from_iter(s.chars()).eq(&digits[0]))?
+        .chars());
+
+    // Find the '5'
+    // The difference with the four is 2 wires
+    digits[5] = HashSet::from_iter(digit_strings
+        .iter()
+        .find(|s| s.len() == 5
+            && !HashSet::from_iter(s.chars()).eq(&digits[3])
+            && HashSet::from_iter(s.chars()).difference(&digits[4]).count() == 2)?
+        .chars());
+
+    // Find the '2'
+    // The last one remaining with 5 wires
+    digits[2] = HashSet::from_iter(digit_strings
+        .iter()
+        .find(|s| s.len() == 5
+            && !HashSet::from_iter(s.chars()).eq(&digits[3])
+            && !HashSet::from_iter(s.chars()).eq(&digits[5]))?
+        .chars());
+
+
+    Some(digits)
+}
+
+/// The number shown by the patterns `currentdisplay`, with the wires of each digit.
+fn read_display(digits: &[HashSet<char>], currentdisplay: &str) -> Option<usize> {
+    let mut tot = 0;
+    for (i, d) in currentdisplay.split(' ').rev().enumerate() {
+        let set: HashSet<char> = HashSet::from_iter(d.chars());
+        let pos = digits.iter().enumerate().find(|(_, s)| (*s).eq(&set))?.0;
+        tot += usize::pow(10, i as u32) * pos;
+    }
+
+    Some(tot)
+}
+
+/// One line of notes: the ten unique signal patterns and the four digit output value
+#[derive(Debug)]
+pub struct Note {
+    /// The ten unique signal patterns, separated by spaces
+    pub alldigits: String,
+    /// The four patterns of the output value, separated by spaces
+    pub currentdisplay: String,
+}
+
+/// The notes, one `patterns | output` per line.
+pub fn parse(lines: &[String]) -> Result<Vec<Note>, AocError> {
+    let mut notes: Vec<Note> = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        let (alldigits, currentdisplay) = line
+            .split_once(" | ")
+            .ok_or_else(|| AocError::at_end(idx + 1, line, "' | ' between patterns and output"))?;
+        if alldigits.split(' ').count() != 10 {
+            return Err(AocError::at(idx + 1, line, alldigits, "10 signal patterns"));
+        }
+        if currentdisplay.split(' ').count() != 4 {
+            return Err(AocError::at(idx + 1, line, currentdisplay, "4 output digits"));
+        }
+        if let Some(pos) = line.find(|c: char| !matches!(c, 'a'..='g' | ' ' | '|')) {
+            return Err(AocError::at_first_char(idx + 1, line, &line[pos..], "a segment 'a' to 'g'"));
+        }
+        let digits = wiring(alldigits).ok_or_else(|| AocError::at(idx + 1, line, alldigits, "the patterns of the ten digits"))?;
+        if read_display(&digits, currentdisplay).is_none() {
+            return Err(AocError::at(idx + 1, line, currentdisplay, "4 output digits among the patterns"));
+        }
+        notes.push(Note {
+            alldigits: alldigits.to_string(),
+            currentdisplay: currentdisplay.to_string(),
+        });
+    }
+
+    Ok(notes)
+}
+
+/// Sum of the output values.
+pub fn star_two(notes: &[Note]) -> usize {
+    let mut count: usize = 0;
+    for note in notes {
+        count += parse_display(&note.alldigits, &note.currentdisplay).expect("Notes that parse can be decoded");
+    }
+
+    count
+}
+
+/// Number of 1s, 4s, 7s and 8s in the output values, the digits with a unique number of segments.
+pub fn star_one(notes: &[Note]) -> usize {
+    let mut count = 0;
+    for note in notes {
+        for digit in note.currentdisplay.split(' ') {
+            match digit.len() {
+                2 => count += 1, // digit 1
+                4 => count += 1, // digit 4
+                3 => count += 1, // digit 7
+                7 => count += 1, // digit 8
+                _ => (),
+            }
+        }
+    }
+
+    count as usize
+}
+
+/// The example from the puzzle description
+pub static TEST_DATA: &str = include_str!("../../examples/day08/example.txt");
+
+/// The puzzle, for the runner.
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Vec<Note>;
+
+    fn example(&self) -> &'static str {
+        TEST_DATA
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Vec<Note>, AocError> {
+        parse(lines)
+    }
+
+    fn part_one(&self, notes: &Vec<Note>) -> Answer {
+        star_one(notes).into()
+    }
+
+    fn part_two(&self, notes: &Vec<Note>) -> Answer {
+        star_two(notes).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TEST_DATA;
+
+    #[test]
+    fn test_star_one() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let ans = super::star_one(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 26);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let ans = super::star_two(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 61229);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let note = |text: &str| super::parse(&[String::from(text)]).unwrap_err().to_string();
+        assert_eq!(
+            note("ab ab abc abcd bcdef acdfg abcdf abcdef bcdefg abcefg | ab ab ab ab"),
+            "1:1: expected the patterns of the ten digits, found 'ab ab abc abcd bcdef acdfg abcdf abcdef bcdefg abcefg'"
+        );
+        assert_eq!(
+            note("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb ce"),
+            "1:62: expected 4 output digits among the patterns, found 'cdfeb fcadb cdfeb ce'"
+        );
+    }
+}
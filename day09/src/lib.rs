@@ -0,0 +1,196 @@
+//! Day 9: Smoke Basin. Finds the low points of a height map and the basins that flow
+//! down into them.
+
+#![warn(missing_docs)]
+
+use std::vec::Vec;
+
+use common::parse;
+use common::render::{self, Animation};
+use common::{Answer, AocError, Grid, Solution};
+
+/// Marks the basin around `pos` in `basinmap` and returns its size. Keeps the positions
+/// still to visit on a stack, as a basin can be too large to recurse into.
+fn flood_fill(map: &Grid<u32>, basinmap: &mut Grid<u32>, pos: (usize, usize), basin: u32) -> u32 {
+    let mut size = 0;
+    let mut todo = vec![pos];
+    basinmap[pos] = basin;
+    while let Some(pos) = todo.pop() {
+        size += 1;
+        for n in map.neighbors4(pos) {
+            if map[n] != 9 && basinmap[n] == 0 {
+                basinmap[n] = basin;
+                todo.push(n);
+            }
+        }
+    }
+
+    size
+}
+
+/// The height map, a digit per position.
+pub fn parse(lines: &[String]) -> Result<Grid<u32>, AocError> {
+    parse::digit_grid(lines, "a height digit")
+}
+
+/// The basin number of every position, 0 for the walls of height 9, and the sizes of
+/// basins 1 and up.
+pub fn find_basins(map: &Grid<u32>) -> (Grid<u32>, Vec<u32>) {
+    let mut basinmap: Grid<u32> = Grid::new(map.width(), map.height(), 0);
+    let mut basincounts: Vec<u32> = Vec::new();
+    for pos in map.positions() {
+        if map[pos] == 9 {
+            continue;
+        }
+
+        if basinmap[pos] != 0 {
+            continue;
+        }
+
+        let basin = basincounts.len() as u32 + 1;
+        basincounts.push(flood_fill(map, &mut basinmap, pos, basin));
+    }
+
+    (basinmap, basincounts)
+}
+
+/// Product of the sizes of the three largest basins.
+pub fn star_two(map: &Grid<u32>) -> u128 {
+    let (_, mut basincounts) = find_basins(map);
+    basincounts.sort_by(|a, b| a.cmp(b).reverse());
+    basincounts.truncate(3);
+
+    basincounts.iter().map(|count| *count as u128).product()
+}
+
+/// The positions lower than all their neighbours.
+pub fn find_lowpoints(map: &Grid<u32>) -> Vec<(usize, usize)> {
+    let mut lowpoints: Vec<(usize,usize)> = Vec::new();
+    for (pos, height) in map.cells() {
+        let lowest = map.neighbors4(pos).all(|n| map[n] > *height);
+        if lowest {
+            lowpoints.push(pos);
+        }
+    }
+
+    lowpoints
+}
+
+/// Sum of the risk levels of the low points, their height plus one.
+pub fn star_one(map: &Grid<u32>) -> u32 {
+    let lowpoints = find_lowpoints(map);
+
+    lowpoints.iter().map(|pos| map[*pos] + 1).sum()
+}
+
+/// Palette indices: the heights 0 to 9, then the low points, the three largest basins
+/// and the other basins.
+const LOWPOINT: u8 = 10;
+const LARGEST_BASIN: u8 = 11;
+const OTHER_BASIN: u8 = 14;
+
+/// The height map with its low points, then with the basins, the largest three highlighted.
+pub fn render(map: &Grid<u32>) -> Animation {
+    let mut palette = render::gradient([10, 30, 90], [200, 215, 235], 10);
+    palette.extend([[230, 30, 30], [255, 150, 0], [60, 200, 60], [190, 80, 220], [60, 110, 130]]);
+    let mut anim = Animation::new(palette).with_delay(100);
+
+    let lowpoints = find_lowpoints(map);
+    let mut heights = map.map(|height| *height as u8);
+    for pos in &lowpoints {
+        heights[*pos] = LOWPOINT;
+    }
+    anim.push_frame(heights);
+
+    let (basinmap, basincounts) = find_basins(map);
+    let mut largest: Vec<u32> = (1..=basincounts.len() as u32).collect();
+    largest.sort_by_key(|basin| std::cmp::Reverse(basincounts[*basin as usize - 1]));
+    let mut basins = basinmap.map(|basin| match largest.iter().take(3).position(|b| b == basin) {
+        _ if *basin == 0 => 9,
+        Some(rank) => LARGEST_BASIN + rank as u8,
+        None => OTHER_BASIN,
+    });
+    for pos in &lowpoints {
+        basins[*pos] = LOWPOINT;
+    }
+    anim.push_frame(basins);
+
+    anim
+}
+
+/// The example from the puzzle description
+pub static TEST_DATA: &str = include_str!("../../examples/day09/example.txt");
+
+/// The puzzle, for the runner.
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Grid<u32>;
+
+    fn example(&self) -> &'static str {
+        TEST_DATA
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Grid<u32>, AocError> {
+        parse(lines)
+    }
+
+    fn part_one(&self, map: &Grid<u32>) -> Answer {
+        star_one(map).into()
+    }
+
+    // Products of three basin sizes fit in an i128
+    fn part_two(&self, map: &Grid<u32>) -> Answer {
+        Answer::Number(star_two(map) as i128)
+    }
+
+    fn render(&self, map: &Grid<u32>) -> Option<Animation> {
+        Some(render(map))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TEST_DATA;
+
+    #[test]
+    fn test_star_one() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let ans = super::star_one(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 15);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let ans = super::star_two(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 1134);
+    }
+
+    #[test]
+    fn large_basins() {
+        // Three basins of 2000 between walls of 9s
+        let lines: Vec<String> = (0..5).map(|y| if y % 2 == 0 { "0" } else { "9" }.repeat(2000)).collect();
+        assert_eq!(super::star_two(&super::parse(&lines).unwrap()), 2000 * 2000 * 2000);
+
+        // A single basin too large to fill recursively
+        let lines: Vec<String> = (0..1000).map(|_| "0".repeat(1000)).collect();
+        let (_, basincounts) = super::find_basins(&super::parse(&lines).unwrap());
+        assert_eq!(basincounts, vec![1000 * 1000]);
+    }
+
+    #[test]
+    fn render() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let anim = super::render(&super::parse(&lines).unwrap());
+        let count = |frame: usize, colour: u8| anim.frames()[frame].iter().filter(|c| **c == colour).count();
+        assert_eq!(count(0, super::LOWPOINT), 4);
+        assert_eq!(count(1, super::LOWPOINT), 4);
+        // The largest basins minus their low points
+        assert_eq!(count(1, super::LARGEST_BASIN), 13);
+        assert_eq!(count(1, super::LARGEST_BASIN + 1) + count(1, super::LARGEST_BASIN + 2), 16);
+    }
+}
@@ -0,0 +1,294 @@
+//! Day 10: Syntax Scoring. Checks the chunks of the navigation subsystem, scoring
+//! the corrupted lines and completing the incomplete ones.
+
+#![warn(missing_docs)]
+
+use std::collections::BTreeMap;
+use std::vec::Vec;
+
+use common::{Answer, AocError, LineReader, Solution};
+
+/// What checking the chunks of a line found.
+#[derive(Debug)]
+pub enum ParseResult {
+    /// The line is corrupted: this closing character does not match the open chunk
+    Illegal(char),
+    /// The line ends with these chunks still open, outermost first
+    Incomplete(Vec<char>),
+    /// All chunks are closed
+    Ok,
+}
+
+fn parse_line(line_nr: usize, line: &str) -> Result<ParseResult, AocError> {
+    let mut parsestack: Vec<char> = Vec::new();
+
+    for (pos, ch) in line.char_indices() {
+        match ch {
+            '(' | '[' | '{' | '<' => {
+                parsestack.push(ch);
+                continue;
+            }
+            ')' | ']' | '}' | '>' => {
+                let open = match ch {
+                    ')' => '(',
+                    ']' => '[',
+                    '}' => '{',
+                    _ => '<',
+                };
+                match parsestack.pop() {
+                    Some(top) if top == open => continue,
+                    Some(_) => return Ok(ParseResult::Illegal(ch)),
+                    None => return Err(AocError::at_first_char(line_nr, line, &line[pos..], "an open chunk before closing one")),
+                }
+            }
+            _ => return Err(AocError::at_first_char(line_nr, line, &line[pos..], "one of '()[]{}<>'")),
+        }
+    }
+
+    if !parsestack.is_empty() {
+        return Ok(ParseResult::Incomplete(parsestack.clone()));
+    }
+
+    Ok(ParseResult::Ok)
+}
+
+/// Checks every line; characters other than brackets are an error.
+pub fn parse(lines: &[String]) -> Result<Vec<ParseResult>, AocError> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| parse_line(idx + 1, line))
+        .collect()
+}
+
+fn syntax_score(ch: char) -> usize {
+    match ch {
+        ')' => 3,
+        ']' => 57,
+        '}' => 1197,
+        '>' => 25137,
+        _ => panic!("Character {} not scored", ch)
+    }
+}
+
+/// The score of closing `chars`, or `None` if it does not fit in 64 bits, which happens
+/// with more than 27 chunks left open.
+fn completion_score(chars: &[char]) -> Option<usize> {
+    chars.iter().rev().try_fold(0usize, |acc, ch| {
+        acc.checked_mul(5)?.checked_add(match ch {
+            '(' => 1,
+            '[' => 2,
+            '{' => 3,
+            '<' => 4,
+            _ => panic!("Invalid character {} on stack", ch),
+        })
+    })
+}
+
+/// Total syntax error score of the corrupted lines.
+pub fn star_one(results: &[ParseResult]) -> usize {
+    let mut total: usize = 0;
+    for result in results {
+        match result {
+            ParseResult::Illegal(ch) => total += syntax_score(*ch),
+            ParseResult::Incomplete(_) => {},
+            ParseResult::Ok => {},
+        }
+    }
+
+    total
+}
+
+/// How often every completion score occurs, to find the middle one without keeping a
+/// score per incomplete line.
+#[derive(Default)]
+struct Scores {
+    counts: BTreeMap<usize, usize>,
+    /// Number of scores too large to fit, which are larger than all others
+    too_large: usize,
+}
+
+impl Scores {
+    fn add(&mut self, score: Option<usize>) {
+        match score {
+            Some(score) => *self.counts.entry(score).or_insert(0) += 1,
+            None => self.too_large += 1,
+        }
+    }
+
+    /// The middle score, or why there is none: no line was incomplete, or the middle
+    /// score is one of those too large to fit.
+    fn middle(&self) -> Result<usize, &'static str> {
+        let total: usize = self.counts.values().sum::<usize>() + self.too_large;
+        if total == 0 {
+            return Err("no line is incomplete");
+        }
+
+        let mut idx = total / 2;
+        for (score, count) in &self.counts {
+            if idx < *count {
+                return Ok(*score);
+            }
+            idx -= count;
+        }
+        Err("the middle completion score does not fit in 64 bits")
+    }
+}
+
+/// Middle score of completing the incomplete lines; complete lines need no completion.
+/// Says why there is none if no line is incomplete or the score is too large.
+pub fn star_two(results: &[ParseResult]) -> Result<usize, &'static str> {
+    let mut scores = Scores::default();
+    for result in results {
+        match result {
+            ParseResult::Illegal(_) => {},
+            ParseResult::Incomplete(chars) => scores.add(completion_score(chars)),
+            ParseResult::Ok => {},
+        }
+    }
+
+    scores.middle()
+}
+
+/// Both stars in a single pass. To find the middle completion score, the number of
+/// times every distinct score occurs is kept, so memory grows with the number of
+/// different incomplete lines, not with the length of the input.
+pub fn solve_stream(lines: &mut LineReader) -> Result<(usize, Result<usize, &'static str>), AocError> {
+    let mut total: usize = 0;
+    let mut scores = Scores::default();
+    while let Some((line_nr, line)) = lines.next_line() {
+        match parse_line(line_nr, line)? {
+            ParseResult::Illegal(ch) => total += syntax_score(ch),
+            ParseResult::Incomplete(chars) => scores.add(completion_score(&chars)),
+            ParseResult::Ok => {},
+        }
+    }
+
+    Ok((total, scores.middle()))
+}
+
+/// The example from the puzzle description
+pub static TEST_DATA: &str = include_str!("../../examples/day10/example.txt");
+
+/// The puzzle, for the runner.
+pub struct Day10;
+
+fn middle_answer(middle: Result<usize, &str>) -> Answer {
+    match middle {
+        Ok(score) => score.into(),
+        Err(why) => Answer::Unsolved(String::from(why)),
+    }
+}
+
+impl Solution for Day10 {
+    type Input = Vec<ParseResult>;
+
+    fn example(&self) -> &'static str {
+        TEST_DATA
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Vec<ParseResult>, AocError> {
+        parse(lines)
+    }
+
+    fn part_one(&self, results: &Vec<ParseResult>) -> Answer {
+        star_one(results).into()
+    }
+
+    fn part_two(&self, results: &Vec<ParseResult>) -> Answer {
+        middle_answer(star_two(results))
+    }
+
+    fn solve_stream(&self, lines: &mut LineReader) -> Option<Result<(Answer, Answer), AocError>> {
+        Some(solve_stream(lines).map(|(one, two)| (one.into(), middle_answer(two))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TEST_DATA;
+
+    #[test]
+    fn test_star_one() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let ans = super::star_one(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 26397);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let ans = super::star_two(&super::parse(&lines).unwrap());
+        assert_eq!(ans, Ok(288957));
+    }    
+
+    #[test]
+    fn stream() {
+        let mut lines = common::LineReader::new(std::io::Cursor::new(TEST_DATA));
+
+        let ans = super::solve_stream(&mut lines).unwrap();
+        assert_eq!(ans, (26397, Ok(288957)));
+    }
+
+    #[test]
+    fn complete_and_corrupted() {
+        let lines: Vec<String> = ["()", "{([(<{}[<>[]}>{[]{[(<()>", "[<(", "<{}>"].iter().map(|x| x.to_string()).collect();
+        let results = super::parse(&lines).unwrap();
+        assert_eq!(super::star_one(&results), 1197);
+        assert_eq!(super::star_two(&results).ok(), super::completion_score(&['[', '<', '(']));
+
+        // Without incomplete lines both ways of solving agree there is no middle score
+        let text = "()\n{([(<{}[<>[]}>{[]{[(<()>\n";
+        let lines: Vec<String> = text.lines().map(|x| x.to_string()).collect();
+        assert_eq!(super::star_two(&super::parse(&lines).unwrap()), Err("no line is incomplete"));
+        let ans = super::solve_stream(&mut common::LineReader::new(std::io::Cursor::new(text))).unwrap();
+        assert_eq!(ans, (1197, Err("no line is incomplete")));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = super::parse_line(3, "[<>]]").unwrap_err();
+        assert_eq!(err.to_string(), "3:5: expected an open chunk before closing one, found ']'");
+        let err = super::parse_line(1, "[<x").unwrap_err();
+        assert_eq!(err.to_string(), "1:3: expected one of '()[]{}<>', found 'x'");
+    }
+
+    #[test]
+    fn repeated_scores() {
+        let mut scores = super::Scores::default();
+        for score in [5, 1, 5, 5, 3, 1] {
+            scores.add(Some(score));
+        }
+        assert_eq!(scores.counts.len(), 3);
+        assert_eq!(scores.middle(), Ok(5));
+
+        // Scores too large to fit come after all others
+        for _ in 0..5 {
+            scores.add(None);
+        }
+        assert_eq!(scores.middle(), Ok(5));
+        scores.add(None);
+        assert_eq!(scores.middle(), Err("the middle completion score does not fit in 64 bits"));
+    }
+
+    #[test]
+    fn many_open_chunks() {
+        assert!(super::completion_score(&['<'; 27]).is_some());
+        assert!(super::completion_score(&['<'; 28]).is_none());
+
+        // Part one is still answered when a line leaves too many chunks open to score
+        let text = format!("{}\n{{([(<{{}}[<>[]}}>{{[]{{[(<()>\n", "<".repeat(28));
+        let lines: Vec<String> = text.lines().map(|x| x.to_string()).collect();
+        let results = super::parse(&lines).unwrap();
+        assert_eq!(super::star_one(&results), 1197);
+        assert_eq!(super::star_two(&results), Err("the middle completion score does not fit in 64 bits"));
+        let ans = super::solve_stream(&mut common::LineReader::new(std::io::Cursor::new(text))).unwrap();
+        assert_eq!(ans, (1197, super::star_two(&results)));
+
+        // Larger scores do not matter if they are not in the middle
+        let lines: Vec<String> = ["<".repeat(28), String::from("<"), String::from("<")].to_vec();
+        assert_eq!(super::star_two(&super::parse(&lines).unwrap()), Ok(4));
+    }
+}
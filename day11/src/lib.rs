@@ -0,0 +1,339 @@
+//! Day 11: Dumbo Octopus. Simulates a grid of octopuses whose flashes of light set
+//! off their neighbours, until they all flash at once.
+
+#![warn(missing_docs)]
+
+use std::collections::HashSet;
+
+use common::params::{self, Param};
+use common::parse;
+use common::render::{self, Animation};
+use common::repl::{self, Command, Session};
+use common::visual::{self, Frame};
+use common::{Answer, AocError, Grid, Solution};
+
+#[allow(dead_code)]
+fn print_map(map: &Grid<usize>) {
+    let chars = map.map(|x| match x {
+        0..=9 => char::from_digit(*x as u32, 10).unwrap(),
+        10 => 'X',
+        _ => 'o',
+    });
+    print!("{}", chars);
+}
+
+/// Advances the energy levels in `map` by one step; returns how many octopuses flashed.
+pub fn step(map: &mut Grid<usize>) -> usize {
+    // Increase energy level
+    for val in map.iter_mut() {
+        *val += 1;
+    }
+
+    // Flash anything > 9 once
+    let mut flashed: HashSet<(usize, usize)> = HashSet::new();
+    loop
+    {
+        let flash = map
+            .cells()
+            .find(|(pos, val)| **val > 9 && !flashed.contains(pos))
+            .map(|(pos, _)| pos);
+
+        if let Some(pos) = flash {
+            flashed.insert(pos);
+            for n in map.neighbors8(pos) {
+                map[n] += 1;
+            }
+        } else {
+            break;
+        }
+    }
+
+    // Set flashed to 0
+    for pos in &flashed {
+        map[*pos] = 0;
+    }
+
+    // Return count of flashes
+    flashed.len()
+}
+
+/// The energy levels, a digit per octopus.
+pub fn parse(lines: &[String]) -> Result<Grid<usize>, AocError> {
+    parse::digit_grid(lines, "an energy level digit")
+}
+
+/// Number of flashes in the first 100 steps.
+pub fn star_one(map: &Grid<usize>) -> usize {
+    flashes(map, 100)
+}
+
+/// The total number of flashes in the first `steps` steps.
+pub fn flashes(map: &Grid<usize>, steps: usize) -> usize {
+    let mut map = map.clone();
+
+    let mut flashcount = 0;
+    for _ in 0..steps {
+        flashcount += step(&mut map);
+    }
+
+    flashcount
+}
+
+/// The first step in which all octopuses flash, or `None` if they never do. The
+/// octopuses end up repeating their levels, so stepping stops once a repetition is
+/// found (with Brent's algorithm) before they all flashed.
+pub fn star_two(map: &Grid<usize>) -> Option<usize> {
+    let mut map = map.clone();
+    let mapsize = map.len();
+
+    let mut saved = map.clone();
+    let mut stepcount = 0;
+    loop {
+        stepcount += 1;
+        if step(&mut map) == mapsize {
+            return Some(stepcount);
+        }
+        if map == saved {
+            return None;
+        }
+        // Saving at powers of two lets a cycle of any length be caught
+        if stepcount.is_power_of_two() {
+            saved = map.clone();
+        }
+    }
+}
+
+/// Steps shown at most, in case the octopuses never all flash at once
+const MAX_SHOWN_STEPS: usize = 2000;
+
+/// The map before the first step and after every step until all octopuses flash at once.
+fn steps_until_synchronized(map: &Grid<usize>) -> Vec<Grid<usize>> {
+    let mut map = map.clone();
+    let mut maps = vec![map.clone()];
+    for _ in 0..MAX_SHOWN_STEPS {
+        let flashes = step(&mut map);
+        maps.push(map.clone());
+        if flashes == map.len() {
+            break;
+        }
+    }
+    maps
+}
+
+/// A frame per step until all octopuses flash at once; the ones that just flashed are
+/// white, the others get brighter as their energy level goes up.
+pub fn render(map: &Grid<usize>) -> Animation {
+    let mut palette = vec![[255, 255, 255]];
+    palette.extend(render::gradient([0, 0, 40], [40, 140, 255], 9));
+    let mut anim = Animation::new(palette);
+    for map in steps_until_synchronized(map) {
+        anim.push_frame(map.map(|energy| *energy as u8));
+    }
+    anim
+}
+
+/// Like `render`, as text: the octopuses that just flashed are yellow, the others grey.
+pub fn visualize(map: &Grid<usize>) -> Vec<Frame> {
+    steps_until_synchronized(map)
+        .iter()
+        .enumerate()
+        .map(|(idx, map)| {
+            let flashes = map.iter().filter(|energy| **energy == 0).count();
+            let text = visual::grid_text(map, |energy| match *energy {
+                0 => ('0', 226),
+                energy => (char::from_digit(energy as u32, 10).unwrap_or('+'), 234 + 2 * energy.min(9) as u8),
+            });
+            Frame::new(format!("Step {}: {} flashes", idx, flashes), text)
+        })
+        .collect()
+}
+
+/// The octopuses being stepped through in `aoc repl`.
+struct Octopuses {
+    map: Grid<usize>,
+    steps: usize,
+    flashes: usize,
+}
+
+static COMMANDS: &[Command] = &[Command { name: "step", args: "[N]", help: "runs N steps, one by default" }];
+
+impl Session for Octopuses {
+    fn commands(&self) -> &'static [Command] {
+        COMMANDS
+    }
+
+    fn run(&mut self, name: &str, args: &str) -> Result<String, String> {
+        match name {
+            "step" => {
+                let mut flashes = 0;
+                for _ in 0..repl::repeat(name, args, 1..=1_000_000)? {
+                    flashes += step(&mut self.map);
+                    self.steps += 1;
+                }
+                self.flashes += flashes;
+                Ok(format!("{} flashes\n{}", flashes, self.show()))
+            }
+            _ => Err(format!("unknown command '{}'", name)),
+        }
+    }
+
+    fn show(&self) -> String {
+        format!("After step {}, {} flashes in total:\n{}", self.steps, self.flashes, self.map)
+    }
+}
+
+/// The example from the puzzle description
+pub static TEST_DATA: &str = include_str!("../../examples/day11/example.txt");
+
+/// The puzzle, for the runner, with the number of steps counted for part one.
+pub struct Day11 {
+    /// Steps whose flashes part one counts
+    pub steps: usize,
+}
+
+impl Default for Day11 {
+    fn default() -> Day11 {
+        Day11 { steps: 100 }
+    }
+}
+
+static PARAMS: &[Param] = &[Param { name: "steps", default: "100", help: "steps whose flashes part one counts" }];
+
+impl Solution for Day11 {
+    type Input = Grid<usize>;
+
+    fn example(&self) -> &'static str {
+        TEST_DATA
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Grid<usize>, AocError> {
+        parse(lines)
+    }
+
+    fn part_one(&self, map: &Grid<usize>) -> Answer {
+        flashes(map, self.steps).into()
+    }
+
+    fn part_two(&self, map: &Grid<usize>) -> Answer {
+        match star_two(map) {
+            Some(steps) => steps.into(),
+            None => Answer::Unsolved(String::from("the octopuses never all flash at once")),
+        }
+    }
+
+    fn render(&self, map: &Grid<usize>) -> Option<Animation> {
+        Some(render(map))
+    }
+
+    fn visualize(&self, map: &Grid<usize>) -> Option<Vec<Frame>> {
+        Some(visualize(map))
+    }
+
+    fn session(&self, map: &Grid<usize>) -> Option<Box<dyn Session>> {
+        Some(Box::new(Octopuses { map: map.clone(), steps: 0, flashes: 0 }))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "steps" => self.steps = params::number(name, value, 0..=1_000_000)?,
+            _ => return Err(format!("unknown parameter '{}'", name)),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TEST_DATA;
+
+    static SIMPLE_TEST_DATA: &str = "11111
+19991
+19191
+19991
+11111";
+
+    #[test]
+    fn test_simple_example() {
+        let lines: Vec<String> = SIMPLE_TEST_DATA
+            .lines()
+            .map(|x| x.to_string())
+            .collect();
+
+        let mut map = super::parse(&lines).unwrap();
+        println!("=== 0");
+        super::print_map(&map);
+        let ans = super::step(&mut map);
+        println!("=== 1");
+        super::print_map(&map);
+        super::step(&mut map);
+        println!("=== 2");
+        super::print_map(&map);
+        assert_eq!(ans, 9);
+    }
+
+
+    #[test]
+    fn test_star_one() {
+        let lines: Vec<String> = TEST_DATA
+            .lines()
+            .map(|x| x.to_string())
+            .collect();
+
+        let ans = super::star_one(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 1656);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let lines: Vec<String> = TEST_DATA
+            .lines()
+            .map(|x| x.to_string())
+            .collect();
+
+        let ans = super::star_two(&super::parse(&lines).unwrap());
+        assert_eq!(ans, Some(195));
+    }
+
+    #[test]
+    fn never_synchronized() {
+        let map = super::parse(&[String::from("19")]).unwrap();
+        assert_eq!(super::star_two(&map), None);
+    }
+
+    #[test]
+    fn render() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let anim = super::render(&super::parse(&lines).unwrap());
+        assert_eq!(anim.frames().len(), 195 + 1);
+        assert!(anim.frames()[195].iter().all(|energy| *energy == 0));
+    }
+
+    #[test]
+    fn visualize() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let frames = super::visualize(&super::parse(&lines).unwrap());
+        assert_eq!(frames.len(), 195 + 1);
+        assert_eq!(frames[195].caption, "Step 195: 100 flashes");
+        assert_eq!(frames[0].text.lines().count(), 10);
+    }
+
+    #[test]
+    fn session() {
+        use common::Solution;
+
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+        let mut session = super::Day11::default().session(&super::parse(&lines).unwrap()).unwrap();
+
+        assert!(session.run("step", "").unwrap().starts_with("0 flashes\n"));
+        let out = session.run("step", "9").unwrap();
+        assert!(out.starts_with("204 flashes\nAfter step 10, 204 flashes in total:\n0481112976\n0031112009\n"), "{}", out);
+        assert!(session.run("step", "x").is_err());
+        assert!(session.run("jump", "").is_err());
+    }
+}
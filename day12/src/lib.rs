@@ -0,0 +1,267 @@
+//! Day 12: Passage Pathing. Counts the paths through a cave system that visit small
+//! caves at most once, or one of them twice.
+
+#![warn(missing_docs)]
+
+use std::collections::{HashMap, HashSet};
+use std::vec::Vec;
+
+use common::rng::Rng;
+use common::search::dfs_paths;
+use common::{parse, Answer, AocError, Solution};
+
+/// The caves connected to each cave, from lines of `a-b` connections.
+pub fn parse(lines: &[String]) -> Result<HashMap<String, Vec<String>>, AocError> {
+    let mut result: HashMap<String, Vec<String>> = HashMap::new();
+
+    for (idx, line) in lines.iter().enumerate() {
+        let (cave_a, cave_b) = parse::edge(idx + 1, line, "'-' between two caves")?;
+        for cave in [cave_a, cave_b] {
+            if cave.is_empty() || !(cave.chars().all(|c| c.is_ascii_lowercase()) || cave.chars().all(|c| c.is_ascii_uppercase())) {
+                return Err(AocError::at(idx + 1, line, cave, "a cave name in lower or upper case"));
+            }
+        }
+        // Connected big caves would allow endless paths
+        if !is_small(cave_a) && !is_small(cave_b) {
+            return Err(AocError::at(idx + 1, line, line, "a small cave on at least one side"));
+        }
+        if !result.contains_key(cave_a) {
+            result.insert(cave_a.to_string(), Vec::new());
+        }
+        result.get_mut(cave_a).unwrap().push(cave_b.to_string());
+        if !result.contains_key(cave_b) {
+            result.insert(cave_b.to_string(), Vec::new());
+        }
+        result.get_mut(cave_b).unwrap().push(cave_a.to_string());
+    }
+
+    if !result.contains_key("start") {
+        return Err(AocError::new(lines.len() + 1, 1, "", "a connection to the 'start' cave"));
+    }
+
+    Ok(result)
+}
+
+fn is_small(cave: &str) -> bool {
+    cave.chars().all(|c: char| c.is_ascii_lowercase())
+}
+
+/// All paths from start to end; `revisit` tells if, given the path so far, a small cave
+/// already on it may be entered again.
+fn walk_paths<'a, F>(system: &'a HashMap<String, Vec<String>>, revisit: F) -> Vec<Vec<&'a str>>
+where
+    F: Fn(&[&str]) -> bool,
+{
+    let successors = |path: &[&'a str]| {
+        let cur = path[path.len() - 1];
+        system[cur]
+            .iter()
+            .map(|connected| connected.as_str())
+            .filter(|connected| *connected != "start")
+            .filter(|connected| !is_small(connected) || !path.contains(connected) || revisit(path))
+            .collect::<Vec<&str>>()
+    };
+    let (paths, _) = dfs_paths("start", successors, |cave| *cave == "end");
+
+    paths
+}
+
+/// Number of paths from start to end that visit small caves at most once.
+pub fn star_one(system: &HashMap<String, Vec<String>>) -> usize {
+    let paths = walk_paths(system, |_| false);
+
+    paths.len()
+}
+
+/// Number of paths from start to end that visit a single small cave twice and the
+/// other small caves at most once.
+pub fn star_two(system: &HashMap<String, Vec<String>>) -> usize {
+    // A single small cave may be visited twice, so only if no small cave was yet
+    let paths = walk_paths(system, |path| {
+        !path
+            .iter()
+            .enumerate()
+            .any(|(idx, cave)| is_small(cave) && path[idx + 1..].contains(cave))
+    });
+
+    paths.len()
+}
+
+/// Name number `idx` of `count`, all as long as needed to keep them unique.
+fn cave_name(idx: usize, count: usize) -> String {
+    let mut len = 2;
+    while 26usize.pow(len) < count {
+        len += 1;
+    }
+    (0..len)
+        .rev()
+        .map(|pos| (b'a' + (idx / 26usize.pow(pos) % 26) as u8) as char)
+        .collect()
+}
+
+/// A connected cave system with `size` caves besides start and end (at least two). Big
+/// caves are never connected to each other, as that would allow endless paths. Note
+/// that the number of paths grows very quickly with the size.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let count = size.max(2);
+    // The first cave is small, so every big one has a small cave to connect to
+    let mut caves: Vec<String> = (0..count + 2)
+        .map(|idx| cave_name(idx, count + 2))
+        .filter(|name| name != "start" && name != "end")
+        .take(count)
+        .enumerate()
+        .map(|(idx, name)| if idx > 0 && rng.chance(1, 4) { name.to_ascii_uppercase() } else { name })
+        .collect();
+    let small: Vec<usize> = (0..count).filter(|idx| is_small(&caves[*idx])).collect();
+    caves.push(String::from("start"));
+    caves.push(String::from("end"));
+
+    let mut edges: Vec<(usize, usize)> = Vec::new();
+    let mut seen: HashSet<(usize, usize)> = HashSet::new();
+    let mut connect = |a: usize, b: usize| {
+        if a != b && seen.insert((a.min(b), a.max(b))) {
+            edges.push((a, b));
+        }
+    };
+
+    // A spanning tree first, so every cave can be reached
+    for (idx, cave) in caves.iter().enumerate().take(count).skip(1) {
+        let other = if is_small(cave) {
+            rng.range(0..idx)
+        } else {
+            let before = small.iter().filter(|s| **s < idx).count();
+            small[rng.range(0..before)]
+        };
+        connect(idx, other);
+    }
+    for _ in 0..count / 2 {
+        let (a, b) = (rng.range(0..count), rng.range(0..count));
+        if is_small(&caves[a]) || is_small(&caves[b]) {
+            connect(a, b);
+        }
+    }
+    for end in [count, count + 1] {
+        for _ in 0..rng.range(1..3) {
+            connect(end, rng.range(0..count));
+        }
+    }
+
+    edges.iter().map(|(a, b)| format!("{}-{}\n", caves[*a], caves[*b])).collect()
+}
+
+/// The example from the puzzle description
+pub static TEST_DATA: &str = include_str!("../../examples/day12/example.txt");
+
+/// The puzzle, for the runner.
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = HashMap<String, Vec<String>>;
+
+    fn example(&self) -> &'static str {
+        TEST_DATA
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<HashMap<String, Vec<String>>, AocError> {
+        parse(lines)
+    }
+
+    fn part_one(&self, system: &HashMap<String, Vec<String>>) -> Answer {
+        star_one(system).into()
+    }
+
+    fn part_two(&self, system: &HashMap<String, Vec<String>>) -> Answer {
+        star_two(system).into()
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TEST_DATA;
+
+    static SIMPLE_TEST_DATA: &str = "start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end";
+
+    #[test]
+    fn simple() {
+        let lines: Vec<String> = SIMPLE_TEST_DATA
+            .lines()
+            .map(|x| x.to_string())
+            .collect();
+
+        let ans = super::star_one(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 10);
+    }
+
+
+
+    #[test]
+    fn test_star_one() {
+        let lines: Vec<String> = TEST_DATA
+            .lines()
+            .map(|x| x.to_string())
+            .collect();
+
+        let ans = super::star_one(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 19);
+    }
+
+    #[test]
+    fn simple_star_two() {
+        let lines: Vec<String> = SIMPLE_TEST_DATA
+            .lines()
+            .map(|x| x.to_string())
+            .collect();
+
+        let ans = super::star_two(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 36);
+    }
+
+
+    #[test]
+    fn test_star_two() {
+        let lines: Vec<String> = TEST_DATA
+            .lines()
+            .map(|x| x.to_string())
+            .collect();
+
+        let ans = super::star_two(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 103);
+    }
+
+    #[test]
+    fn generate() {
+        let input = super::generate(&mut common::rng::Rng::new(12), 8);
+        assert_eq!(input, super::generate(&mut common::rng::Rng::new(12), 8));
+
+        let lines: Vec<String> = input.lines().map(|x| x.to_string()).collect();
+        let system = super::parse(&lines).unwrap();
+        assert_eq!(system.len(), 10);
+        assert!(system.contains_key("end"));
+        assert!(super::star_two(&system) >= super::star_one(&system));
+        assert!(super::star_one(&system) > 0);
+
+        assert_eq!(super::cave_name(27, 30), "bb");
+        assert_eq!(super::cave_name(27, 700), "abb");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let lines: Vec<String> = vec![String::from("start-A"), String::from("A-Bc")];
+        let err = super::parse(&lines).unwrap_err();
+        assert_eq!(err.to_string(), "2:3: expected a cave name in lower or upper case, found 'Bc'");
+
+        let lines: Vec<String> = vec![String::from("start-A"), String::from("A-B")];
+        let err = super::parse(&lines).unwrap_err();
+        assert_eq!(err.to_string(), "2:1: expected a small cave on at least one side, found 'A-B'");
+    }
+}
@@ -0,0 +1,481 @@
+//! Day 13: Transparent Origami. Folds a sheet of transparent paper with dots on it
+//! until the dots spell out a code.
+
+#![warn(missing_docs)]
+
+use std::fmt;
+use std::vec::Vec;
+
+use common::ocr::{self, OcrError};
+use common::render::Animation;
+use common::repl::{Command, Session};
+use common::visual::{self, Frame};
+use common::{parse, Answer, AocError, Grid, Solution};
+
+/// The line a fold is made along.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FoldAxis {
+    /// A vertical line, folding the right part to the left
+    X,
+    /// A horizontal line, folding the bottom part up
+    Y,
+}
+
+/// A fold instruction.
+#[derive(Debug, Clone)]
+pub struct Fold {
+    axis: FoldAxis,
+    pos: usize,
+}
+
+impl Fold {
+    /// A fold along `axis` at `pos`.
+    pub fn new(axis: FoldAxis, pos: usize) -> Fold {
+        Fold { axis, pos }
+    }
+}
+
+/// The transparent paper; displays as lines of `#` for dots and `.` for empty positions.
+#[derive(Debug, Clone)]
+pub struct Paper {
+    dots: Grid<bool>,
+}
+
+impl Paper {
+    /// Which positions have a dot.
+    pub fn dots(&self) -> &Grid<bool> {
+        &self.dots
+    }
+}
+
+impl fmt::Display for Paper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.dots.map(|dot| if *dot { '#' } else { '.' }))
+    }
+}
+
+#[allow(dead_code)]
+fn print_paper(paper: &Paper) {
+    print!("{}", paper);
+}
+
+#[allow(dead_code)]
+fn print_folds(folds: &Vec<Fold>) {
+    for f in folds {
+        println!("fold over {}={}", if f.axis == FoldAxis::X { "x" } else { "y" }, f.pos);
+    }
+}
+
+/// Largest width and height of the paper that is accepted; the paper is stored as a
+/// grid, so a single far away dot would otherwise take all memory.
+const MAX_PAPER_SIZE: usize = 4096;
+
+/// The paper and the fold instructions, from lines of `x,y` dots, an empty line and
+/// lines like `fold along y=7`.
+pub fn parse(lines: &[String]) -> Result<(Paper, Vec<Fold>), AocError> {
+    let mut coords: Vec<(usize, usize)> = Vec::new();
+    let mut folds: Vec<Fold> = Vec::new();
+    let mut sections = parse::sections(lines);
+
+    // Paper
+    for (line_nr, line) in sections.expect("at least one dot")?.numbered() {
+        let (x, y) = parse::coordinate(line_nr, line, line)?;
+        if x >= MAX_PAPER_SIZE || y >= MAX_PAPER_SIZE {
+            return Err(AocError::at(line_nr, line, line, &format!("coordinates below {}", MAX_PAPER_SIZE)));
+        }
+        coords.push((x, y));
+    }
+
+    let width = coords.iter().map(|(x, _)| x + 1).max().unwrap();
+    let height = coords.iter().map(|(_, y)| y + 1).max().unwrap();
+    let mut paper = Paper { dots: Grid::new(width, height, false) };
+    for pos in coords {
+        paper.dots[pos] = true;
+    }
+
+    // Folds
+    for (line_nr, line) in sections.expect_last("at least one fold instruction")?.numbered() {
+        let instr = line
+            .strip_prefix("fold along ")
+            .ok_or_else(|| AocError::at(line_nr, line, line, "'fold along '"))?;
+        let (axis, pos) = parse::split_pair(line_nr, line, instr, "=", "'=' after the fold axis")?;
+        let axis = match axis {
+            "y" => FoldAxis::Y,
+            "x" => FoldAxis::X,
+            _ => return Err(AocError::at(line_nr, line, axis, "fold axis 'x' or 'y'")),
+        };
+        let what = format!("fold position below {}", MAX_PAPER_SIZE);
+        let pos = match parse::number(line_nr, line, pos, &what)? {
+            pos if pos < MAX_PAPER_SIZE => pos,
+            _ => return Err(AocError::at(line_nr, line, pos, &what)),
+        };
+        folds.push(Fold { axis, pos });
+    }
+
+    Ok((paper, folds))
+}
+
+/// Folds the bottom part of `dots` up over row `pos`, which is removed.
+fn fold_up(dots: &mut Grid<bool>, pos: usize) {
+    let height = dots.height();
+
+    // Perform fold
+    let mut offs: usize = 1;
+    while (pos + offs < height) && (pos >= offs) {
+        for x in 0..dots.width() {
+            if dots[(x, pos + offs)] {
+                dots[(x, pos - offs)] = true;
+            }
+        }
+        offs += 1;
+    }
+
+    // Remove folded lines
+    let width = dots.width();
+    dots.crop(width, pos);
+}
+
+/// Folds the paper along one line.
+pub fn perform_fold(paper: &mut Paper, fold: &Fold) {
+    match fold.axis {
+        FoldAxis::Y => fold_up(&mut paper.dots, fold.pos),
+        FoldAxis::X => {
+            // Folding left is folding up with rows and columns swapped
+            let mut dots = paper.dots.transpose();
+            fold_up(&mut dots, fold.pos);
+            paper.dots = dots.transpose();
+        },
+    }
+}
+
+fn count_dots(paper: &Paper) -> usize {
+    paper.dots.iter().filter(|dot| **dot).count()
+}
+
+/// Number of dots after the first fold.
+pub fn star_one(paper: &Paper, folds: &[Fold]) -> usize {
+    let mut paper = paper.clone();
+
+    perform_fold(&mut paper, &folds[0]);
+
+    count_dots(&paper)
+}
+
+/// The paper after all folds.
+pub fn fold_all(paper: &Paper, folds: &[Fold]) -> Paper {
+    let mut paper = paper.clone();
+
+    for f in folds {
+        perform_fold(&mut paper, f);
+    }
+
+    paper
+}
+
+/// The letters on the paper after all folds.
+pub fn star_two(paper: &Paper, folds: &[Fold]) -> Result<String, OcrError> {
+    ocr::recognize(&fold_all(paper, folds).dots)
+}
+
+/// The paper before the first fold and after each of them, with the part of the
+/// image the paper no longer covers in the background colour.
+pub fn render(paper: &Paper, folds: &[Fold]) -> Animation {
+    let mut anim = Animation::new(vec![[30, 30, 30], [240, 230, 200], [20, 40, 120]]).with_delay(100);
+    let mut paper = paper.clone();
+    anim.push_frame(paper.dots.map(|dot| if *dot { 2 } else { 1 }));
+    for f in folds {
+        perform_fold(&mut paper, f);
+        anim.push_frame(paper.dots.map(|dot| if *dot { 2 } else { 1 }));
+    }
+    anim
+}
+
+/// Largest size of the paper in the terminal, in characters; each character shows two
+/// rows of dots.
+const MAX_COLUMNS: usize = 160;
+const MAX_ROWS: usize = 50;
+
+/// The dots as half block characters, scaled down to fit the terminal if needed so
+/// that a character cell covers `scale` by `scale` dots. Returns the text and the scale.
+fn paper_text(dots: &Grid<bool>) -> (String, usize) {
+    let scale = 1.max(dots.width().div_ceil(MAX_COLUMNS)).max(dots.height().div_ceil(2 * MAX_ROWS));
+    let (width, height) = (dots.width().div_ceil(scale), dots.height().div_ceil(scale));
+    let has_dot = |x: usize, y: usize| {
+        (y * scale..(y + 1) * scale).any(|dy| (x * scale..(x + 1) * scale).any(|dx| dots.get((dx, dy)) == Some(&true)))
+    };
+
+    let mut cells = Grid::new(width, height.div_ceil(2), ' ');
+    for (x, y) in cells.positions() {
+        cells[(x, y)] = match (has_dot(x, 2 * y), has_dot(x, 2 * y + 1)) {
+            (true, true) => '█',
+            (true, false) => '▀',
+            (false, true) => '▄',
+            (false, false) => ' ',
+        };
+    }
+    (visual::grid_text(&cells, |ch| (*ch, 229)), scale)
+}
+
+/// The paper before the first fold and after each of them.
+pub fn visualize(paper: &Paper, folds: &[Fold]) -> Vec<Frame> {
+    let mut paper = paper.clone();
+    let mut frames = Vec::new();
+    for idx in 0..=folds.len() {
+        if idx > 0 {
+            perform_fold(&mut paper, &folds[idx - 1]);
+        }
+        let (text, scale) = paper_text(&paper.dots);
+        let mut caption = match idx {
+            0 => String::from("Before folding"),
+            _ => {
+                let fold = &folds[idx - 1];
+                let axis = if fold.axis == FoldAxis::X { "x" } else { "y" };
+                format!("Fold {} of {} along {}={}", idx, folds.len(), axis, fold.pos)
+            }
+        };
+        caption.push_str(&format!(": {}x{}, {} dots", paper.dots.width(), paper.dots.height(), count_dots(&paper)));
+        if scale > 1 {
+            caption.push_str(&format!(" (scaled down {} times)", scale));
+        }
+        frames.push(Frame::new(caption, text));
+    }
+    frames
+}
+
+/// The paper being folded in `aoc repl`, with the folds of the input not made yet.
+struct Folding {
+    paper: Paper,
+    folds: Vec<Fold>,
+    next: usize,
+}
+
+static COMMANDS: &[Command] = &[
+    Command { name: "fold", args: "x=N|y=N", help: "folds along a line" },
+    Command { name: "next", args: "", help: "makes the next fold of the input" },
+    Command { name: "read", args: "", help: "reads the letters on the paper" },
+];
+
+impl Folding {
+    fn fold(&mut self, fold: &Fold) -> Result<String, String> {
+        let (axis, size) = match fold.axis {
+            FoldAxis::X => ("x", self.paper.dots.width()),
+            FoldAxis::Y => ("y", self.paper.dots.height()),
+        };
+        if fold.pos >= size {
+            return Err(format!("{}={} is not on the paper, which is {} long", axis, fold.pos, size));
+        }
+        perform_fold(&mut self.paper, fold);
+        Ok(format!("Folded along {}={}\n{}", axis, fold.pos, self.show()))
+    }
+}
+
+impl Session for Folding {
+    fn commands(&self) -> &'static [Command] {
+        COMMANDS
+    }
+
+    fn run(&mut self, name: &str, args: &str) -> Result<String, String> {
+        match name {
+            "fold" => {
+                let (axis, pos) = args.split_once('=').ok_or("expected x=N or y=N")?;
+                let axis = match axis {
+                    "x" => FoldAxis::X,
+                    "y" => FoldAxis::Y,
+                    _ => return Err(format!("invalid fold axis '{}', expected x or y", axis)),
+                };
+                let pos = pos.parse::<usize>().map_err(|_| format!("invalid fold position '{}'", pos))?;
+                self.fold(&Fold::new(axis, pos))
+            }
+            "next" => {
+                let fold = self.folds.get(self.next).ok_or("all folds of the input are made")?.clone();
+                let out = self.fold(&fold)?;
+                self.next += 1;
+                Ok(out)
+            }
+            "read" => ocr::recognize(&self.paper.dots).map_err(|e| e.to_string()),
+            _ => Err(format!("unknown command '{}'", name)),
+        }
+    }
+
+    fn show(&self) -> String {
+        let dots = count_dots(&self.paper);
+        format!("{}x{}, {} dots:\n{}", self.paper.dots.width(), self.paper.dots.height(), dots, self.paper)
+    }
+}
+
+/// The example from the puzzle description
+pub static TEST_DATA: &str = include_str!("../../examples/day13/example.txt");
+
+/// The puzzle, for the runner.
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = (Paper, Vec<Fold>);
+
+    fn example(&self) -> &'static str {
+        TEST_DATA
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<(Paper, Vec<Fold>), AocError> {
+        parse(lines)
+    }
+
+    fn part_one(&self, (paper, folds): &(Paper, Vec<Fold>)) -> Answer {
+        star_one(paper, folds).into()
+    }
+
+    fn part_two(&self, (paper, folds): &(Paper, Vec<Fold>)) -> Answer {
+        match star_two(paper, folds) {
+            Ok(letters) => letters.into(),
+            // Show the paper, so the letters can still be read by eye
+            Err(e) => Answer::Unsolved(format!("{}\n{}", e, fold_all(paper, folds))),
+        }
+    }
+
+    fn render(&self, (paper, folds): &(Paper, Vec<Fold>)) -> Option<Animation> {
+        Some(render(paper, folds))
+    }
+
+    fn visualize(&self, (paper, folds): &(Paper, Vec<Fold>)) -> Option<Vec<Frame>> {
+        Some(visualize(paper, folds))
+    }
+
+    fn session(&self, (paper, folds): &(Paper, Vec<Fold>)) -> Option<Box<dyn Session>> {
+        Some(Box::new(Folding { paper: paper.clone(), folds: folds.clone(), next: 0 }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TEST_DATA;
+
+    #[test]
+    fn test_star_one() {
+        let lines: Vec<String> = TEST_DATA
+            .lines()
+            .map(|x| x.to_string())
+            .collect();
+
+        let (paper, folds) = super::parse(&lines).unwrap();
+        let ans = super::star_one(&paper, &folds);
+        assert_eq!(ans, 17);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        // The example folds into a square, which is not a letter
+        let (paper, folds) = super::parse(&lines).unwrap();
+        assert_eq!(super::star_two(&paper, &folds), Err(super::OcrError::Height(7)));
+        assert_eq!(super::fold_all(&paper, &folds).to_string(), "#####\n#...#\n#...#\n#...#\n#####\n.....\n.....\n");
+
+        // The runner gets no answer, only the reason and the dots
+        let ans = common::Solution::part_two(&super::Day13, &(paper, folds));
+        assert!(!ans.is_solved());
+        assert!(ans.to_string().starts_with("letters are 6 dots high, the image is 7\n#####\n"), "{}", ans);
+    }
+
+    #[test]
+    fn render() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let (paper, folds) = super::parse(&lines).unwrap();
+        let anim = super::render(&paper, &folds);
+        let dots: Vec<usize> = anim.frames().iter().map(|f| f.iter().filter(|c| **c == 2).count()).collect();
+        assert_eq!(dots, vec![18, 17, 16]);
+        assert_eq!((anim.frames()[2].width(), anim.frames()[2].height()), (5, 7));
+    }
+
+    #[test]
+    fn visualize() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let (paper, folds) = super::parse(&lines).unwrap();
+        let frames = super::visualize(&paper, &folds);
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[1].caption, "Fold 1 of 2 along y=7: 11x7, 17 dots");
+        let text: Vec<&str> = frames[2].text.lines().collect();
+        assert_eq!(text[0], "\x1b[38;5;229m█▀▀▀█\x1b[0m");
+        assert_eq!(text.len(), 4);
+
+        let big = super::Paper { dots: common::Grid::new(400, 90, true) };
+        let frames = super::visualize(&big, &[]);
+        assert_eq!(frames[0].caption, "Before folding: 400x90, 36000 dots (scaled down 3 times)");
+        assert_eq!(frames[0].text.lines().count(), 15);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let lines: Vec<String> = vec![String::from("6,10"), String::from("0;14")];
+        let err = super::parse(&lines).unwrap_err();
+        assert_eq!(err.to_string(), "2:2: expected ',' after X coordinate, found ';'");
+
+        let lines: Vec<String> = vec![String::from("6,10"), String::new(), String::from("fold along z=7")];
+        let err = super::parse(&lines).unwrap_err();
+        assert_eq!(err.to_string(), "3:12: expected fold axis 'x' or 'y', found 'z'");
+
+        let lines: Vec<String> = vec![String::from("6,10"), String::new(), String::from("fold")];
+        let err = super::parse(&lines).unwrap_err();
+        assert_eq!(err.to_string(), "3:1: expected 'fold along ', found 'fold'");
+
+        let lines: Vec<String> = vec![String::from("6,99999999"), String::new(), String::from("fold along y=7")];
+        let err = super::parse(&lines).unwrap_err();
+        assert_eq!(err.to_string(), "1:1: expected coordinates below 4096, found '6,99999999'");
+
+        let lines: Vec<String> = vec![String::from("6,10"), String::new(), String::from("fold along x=18446744073709551615")];
+        let err = super::parse(&lines).unwrap_err();
+        assert_eq!(err.to_string(), "3:14: expected fold position below 4096, found '18446744073709551615'");
+    }
+
+    #[test]
+    fn folding_never_adds_dots() {
+        common::prop::check(
+            300,
+            |rng, size| {
+                let width = rng.range(1..size / 4 + 3);
+                let height = rng.range(1..size / 4 + 3);
+                let mut dots = common::Grid::new(width, height, false);
+                for dot in dots.iter_mut() {
+                    *dot = rng.chance(1, 3);
+                }
+                let fold = if rng.chance(1, 2) {
+                    super::Fold { axis: super::FoldAxis::X, pos: rng.range(0..width) }
+                } else {
+                    super::Fold { axis: super::FoldAxis::Y, pos: rng.range(0..height) }
+                };
+                (super::Paper { dots }, fold)
+            },
+            |(paper, fold)| {
+                let mut folded = paper.clone();
+                super::perform_fold(&mut folded, fold);
+                let (before, after) = (super::count_dots(paper), super::count_dots(&folded));
+                if after > before {
+                    return Err(format!("{} dots became {}", before, after));
+                }
+                common::prop::equal(folded.dots.width() <= paper.dots.width(), true, "widths")?;
+                common::prop::equal(folded.dots.height() <= paper.dots.height(), true, "heights")
+            },
+        );
+    }
+
+    #[test]
+    fn session() {
+        use common::Solution;
+
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+        let mut session = super::Day13.session(&super::parse(&lines).unwrap()).unwrap();
+
+        assert_eq!(session.run("fold", "y=7").unwrap().lines().take(3).collect::<Vec<_>>(), vec![
+            "Folded along y=7",
+            "11x7, 17 dots:",
+            "#.##..#..#.",
+        ]);
+        assert_eq!(session.run("next", ""), Err(String::from("y=7 is not on the paper, which is 7 long")));
+        assert!(session.run("fold", "x=5").unwrap().starts_with("Folded along x=5\n5x7, 16 dots:\n#####\n"));
+        assert!(session.run("next", "").is_err());
+        assert!(session.run("fold", "z=1").is_err());
+        assert!(session.run("fold", "x=").is_err());
+        assert!(session.run("read", "").is_err());
+    }
+}
@@ -0,0 +1,383 @@
+//! Day 14: Extended Polymerization. Grows a polymer by inserting an element between
+//! every pair of elements, step after step, counting pairs instead of building it.
+
+#![warn(missing_docs)]
+
+use std::collections::HashMap;
+
+use common::bigint::BigUint;
+use common::params::{self, Param};
+use common::repl::{self, Command, Session};
+use common::rng::Rng;
+use common::visual::{self, Frame};
+use common::{parse, Answer, AocError, Solution};
+
+/// The element inserted between a pair, and how often the pair occurs. The counts double
+/// about every step, so they outgrow any fixed size integer after a few dozen steps.
+#[derive(Debug, Clone)]
+pub struct Rule {
+    new: char,
+    count: BigUint,
+}
+
+impl Rule {
+    /// The element inserted between the pair.
+    pub fn element(&self) -> char {
+        self.new
+    }
+
+    /// How often the pair occurs in the polymer.
+    pub fn count(&self) -> &BigUint {
+        &self.count
+    }
+}
+
+/// Insertion rules by pair, tracking how often each pair occurs in the polymer
+pub type Rules = HashMap<(char, char), Rule>;
+
+/// Number of occurrences of each element in the polymer
+pub type Counts = HashMap<char, BigUint>;
+
+/// The rules and element counts of the polymer template, from the template, an empty
+/// line and `AB -> C` insertion rules.
+pub fn parse(lines: &[String]) -> Result<(Rules, Counts), AocError> {
+    let mut rules: HashMap<(char, char), Rule> = HashMap::new();
+
+    let mut sections = parse::sections(lines);
+    let template = sections.expect("a polymer template")?;
+    if let Some((line_nr, line)) = template.numbered().nth(1) {
+        return Err(AocError::at(line_nr, line, line, "an empty line after the template"));
+    }
+    let initial = template.lines[0].to_string();
+
+    let mut rule_lines: Vec<(usize, &String)> = Vec::new();
+    for (line_nr, line) in sections.expect_last("pair insertion rules")?.numbered() {
+        let (pair, new) = parse::arrow(line_nr, line, "' -> ' between pair and element")?;
+        let mut pair_chars = pair.chars();
+        let (left, right) = match (pair_chars.next(), pair_chars.next(), pair_chars.next()) {
+            (Some(left), Some(right), None) => (left, right),
+            _ => return Err(AocError::at(line_nr, line, pair, "a pair of two elements")),
+        };
+        let mut new_chars = new.chars();
+        let new = match (new_chars.next(), new_chars.next()) {
+            (Some(new), None) => new,
+            _ => return Err(AocError::at(line_nr, line, new, "a single element")),
+        };
+        rules.insert((left, right), Rule { new, count: BigUint::zero() });
+        rule_lines.push((line_nr, line));
+    }
+
+    // Every insertion creates two new pairs, which need rules of their own
+    for (line_nr, line) in rule_lines {
+        let mut chars = line.chars();
+        let left = chars.next().unwrap();
+        let right = chars.next().unwrap();
+        let new = rules[&(left, right)].new;
+        if !rules.contains_key(&(left, new)) || !rules.contains_key(&(new, right)) {
+            return Err(AocError::at(line_nr, line, line, &format!("rules for {}{} and {}{}", left, new, new, right)));
+        }
+    }
+
+    let one = BigUint::from(1u64);
+    let initial_chars: Vec<char> = initial.chars().collect();
+    for pair in initial_chars.windows(2) {
+        rules
+            .get_mut(&(pair[0], pair[1]))
+            .ok_or_else(|| AocError::at(1, &initial, &initial, &format!("a rule for {}{}", pair[0], pair[1])))?
+            .count += &one;
+    }
+
+    let mut counts: Counts = HashMap::new();
+    for ch in initial.chars() {
+        *counts.entry(ch).or_default() += &one;
+    }
+
+    Ok((rules, counts))
+}
+
+/// Performs one step of pair insertion: returns the rules with the new pair counts and
+/// adds the inserted elements to `counts`.
+pub fn run_gen(rules: &Rules, counts: &mut Counts) -> Rules {
+    let mut new_rules = rules.clone();
+    for (_, rule) in new_rules.iter_mut() {
+        rule.count = BigUint::zero();
+    }
+
+    for ((left, right), rule) in rules {
+        if rule.count.is_zero() {
+            continue;
+        }
+
+        let leftrule = new_rules.get_mut(&(*left, rule.new)).unwrap();
+        leftrule.count += &rule.count;
+        let rightrule = new_rules.get_mut(&(rule.new, *right)).unwrap();
+        rightrule.count += &rule.count;
+
+        *counts.entry(rule.new).or_default() += &rule.count;
+    }
+
+    new_rules
+}
+
+/// The count of the most common element minus that of the least common element after
+/// `count` steps.
+pub fn run(rules: &Rules, counts: &Counts, count: usize) -> BigUint {
+    let mut rules = rules.clone();
+    let mut counts = counts.clone();
+
+    for _ in 0..count {
+        rules = run_gen(&rules, &mut counts);
+    }
+
+    let max = counts.values().max().unwrap();
+    let min = counts.values().min().unwrap();
+
+    max - min
+}
+
+/// Most minus least common element count after 10 steps.
+pub fn star_one(rules: &Rules, counts: &Counts) -> BigUint {
+    run(rules, counts, 10)
+}
+
+/// Most minus least common element count after 40 steps.
+pub fn star_two(rules: &Rules, counts: &Counts) -> BigUint {
+    run(rules, counts, 40)
+}
+
+/// The count of every element after each step up to part two, the most common element
+/// in red and the least common one in blue.
+pub fn visualize(rules: &Rules, counts: &Counts, steps: usize) -> Vec<Frame> {
+    let mut rules = rules.clone();
+    let mut counts = counts.clone();
+    let mut frames = Vec::new();
+    for step in 0..=steps {
+        if step > 0 {
+            rules = run_gen(&rules, &mut counts);
+        }
+        let mut elements: Vec<(&char, &BigUint)> = counts.iter().collect();
+        elements.sort();
+        let max = counts.values().max().unwrap();
+        let min = counts.values().min().unwrap();
+        let text: String = elements
+            .iter()
+            .map(|(element, count)| {
+                let colour = match *count {
+                    count if count == max => 196,
+                    count if count == min => 33,
+                    _ => 250,
+                };
+                visual::big_bar(&element.to_string(), count, max, 60, colour)
+            })
+            .collect();
+        let length: BigUint = counts.values().sum();
+        frames.push(Frame::new(format!("Step {}: length {}, {} - {} = {}", step, length, max, min, max - min), text));
+    }
+    frames
+}
+
+/// Number of different elements, like in the real inputs.
+const ELEMENTS: usize = 10;
+
+/// A polymer template of `size` elements (at least two), with a rule for every pair.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut alphabet: Vec<char> = ('A'..='Z').collect();
+    rng.shuffle(&mut alphabet);
+    let elements = &alphabet[..ELEMENTS];
+
+    let mut out: String = (0..size.max(2)).map(|_| *rng.pick(elements)).collect();
+    out.push_str("\n\n");
+    for left in elements {
+        for right in elements {
+            out.push_str(&format!("{}{} -> {}\n", left, right, rng.pick(elements)));
+        }
+    }
+    out
+}
+
+/// The example from the puzzle description
+pub static TEST_DATA: &str = include_str!("../../examples/day14/example.txt");
+
+/// The puzzle, for the runner, with the number of insertion steps.
+pub struct Day14 {
+    /// Steps for part one
+    pub steps_one: usize,
+    /// Steps for part two
+    pub steps_two: usize,
+}
+
+impl Default for Day14 {
+    fn default() -> Day14 {
+        Day14 { steps_one: 10, steps_two: 40 }
+    }
+}
+
+static PARAMS: &[Param] = &[
+    Param { name: "steps_one", default: "10", help: "pair insertion steps for part one" },
+    Param { name: "steps_two", default: "40", help: "pair insertion steps for part two" },
+    Param { name: "steps", default: "10,40", help: "pair insertion steps for part one and two, or one number for both" },
+];
+
+/// Most steps that can be run; the counts grow by about a bit per step, which makes
+/// every step slower.
+const MAX_STEPS: usize = 10_000;
+
+/// The polymer being grown in `aoc repl`.
+struct Polymer {
+    rules: Rules,
+    counts: Counts,
+    steps: usize,
+}
+
+static COMMANDS: &[Command] = &[Command { name: "gen", args: "[N]", help: "runs N pair insertion steps, one by default" }];
+
+impl Session for Polymer {
+    fn commands(&self) -> &'static [Command] {
+        COMMANDS
+    }
+
+    fn run(&mut self, name: &str, args: &str) -> Result<String, String> {
+        match name {
+            "gen" => {
+                for _ in 0..repl::repeat(name, args, 1..=MAX_STEPS)? {
+                    self.rules = run_gen(&self.rules, &mut self.counts);
+                    self.steps += 1;
+                }
+                Ok(self.show())
+            }
+            _ => Err(format!("unknown command '{}'", name)),
+        }
+    }
+
+    fn show(&self) -> String {
+        let length: BigUint = self.counts.values().sum();
+        let max = self.counts.values().max().unwrap();
+        let min = self.counts.values().min().unwrap();
+        let mut out = format!("Step {}: length {}, {} - {} = {}\n", self.steps, length, max, min, max - min);
+        let mut elements: Vec<(&char, &BigUint)> = self.counts.iter().collect();
+        elements.sort();
+        for (element, count) in elements {
+            out += &format!("{} {}\n", element, count);
+        }
+        out
+    }
+}
+
+impl Solution for Day14 {
+    type Input = (Rules, Counts);
+
+    fn example(&self) -> &'static str {
+        TEST_DATA
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, AocError> {
+        parse(lines)
+    }
+
+    fn part_one(&self, (rules, counts): &Self::Input) -> Answer {
+        run(rules, counts, self.steps_one).into()
+    }
+
+    fn part_two(&self, (rules, counts): &Self::Input) -> Answer {
+        run(rules, counts, self.steps_two).into()
+    }
+
+    fn visualize(&self, (rules, counts): &Self::Input) -> Option<Vec<Frame>> {
+        Some(visualize(rules, counts, self.steps_two))
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn session(&self, (rules, counts): &Self::Input) -> Option<Box<dyn Session>> {
+        Some(Box::new(Polymer { rules: rules.clone(), counts: counts.clone(), steps: 0 }))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "steps_one" => self.steps_one = params::number(name, value, 0..=MAX_STEPS)?,
+            "steps_two" => self.steps_two = params::number(name, value, 0..=MAX_STEPS)?,
+            "steps" => (self.steps_one, self.steps_two) = params::per_part(name, value, 0..=MAX_STEPS)?,
+            _ => return Err(format!("unknown parameter '{}'", name)),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::bigint::BigUint;
+
+    use super::TEST_DATA;
+
+    #[test]
+    fn test_star_one() {
+        let lines: Vec<String> = TEST_DATA
+            .lines()
+            .map(|x| x.to_string())
+            .collect();
+
+        let (rules, counts) = super::parse(&lines).unwrap();
+        let ans = super::star_one(&rules, &counts);
+        assert_eq!(ans, BigUint::from(1588u64));
+    }
+
+    #[test]
+    fn visualize() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let (rules, counts) = super::parse(&lines).unwrap();
+        let frames = super::visualize(&rules, &counts, 40);
+        assert_eq!(frames.len(), 41);
+        assert_eq!(frames[10].caption, "Step 10: length 3073, 1749 - 161 = 1588");
+        assert_eq!(frames[10].text.lines().count(), 4);
+    }
+
+    #[test]
+    fn params() {
+        use common::{Answer, Part, Puzzle, Solution};
+
+        let mut day = super::Day14::default();
+        let input = day.parse_input(&TEST_DATA.lines().map(|x| x.to_string()).collect::<Vec<_>>()).unwrap();
+        assert_eq!(day.solve(&*input, Part::Two), Answer::Number(2188189693529));
+
+        // Past the range of u64 the answer still fits in a number, past i128 it is text
+        day.set_param("steps", "100").unwrap();
+        assert_eq!(day.solve(&*input, Part::One), Answer::Number(2535296262066596202993060773164));
+        day.set_param("steps_two", "200").unwrap();
+        assert!(matches!(day.solve(&*input, Part::Two), Answer::Text(text) if text.len() > 40));
+        assert!(day.set_param("steps", "10001").is_err());
+        day.set_param("steps", "10,40").unwrap();
+        assert_eq!((day.steps_one, day.steps_two), (10, 40));
+    }
+
+    #[test]
+    fn session() {
+        use common::Solution;
+
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+        let mut session = super::Day14::default().session(&super::parse(&lines).unwrap()).unwrap();
+
+        assert!(session.show().starts_with("Step 0: length 4, 2 - 1 = 1\n"));
+        assert_eq!(session.run("gen", "").unwrap(), "Step 1: length 7, 2 - 1 = 1\nB 2\nC 2\nH 1\nN 2\n");
+        assert!(session.run("gen", "9").unwrap().starts_with("Step 10: length 3073, 1749 - 161 = 1588\n"));
+        assert!(session.run("gen", "10001").is_err());
+    }
+
+    #[test]
+    fn generate() {
+        let input = super::generate(&mut common::rng::Rng::new(14), 20);
+        assert_eq!(input, super::generate(&mut common::rng::Rng::new(14), 20));
+
+        let lines: Vec<String> = input.lines().map(|x| x.to_string()).collect();
+        let (rules, counts) = super::parse(&lines).unwrap();
+        assert_eq!(rules.len(), super::ELEMENTS * super::ELEMENTS);
+        assert_eq!(counts.values().sum::<BigUint>(), BigUint::from(20u64));
+        super::star_two(&rules, &counts);
+    }
+}
@@ -0,0 +1,279 @@
+//! Day 15: Chiton. Finds the path of the lowest total risk through a cave full of
+//! chitons, then through the cave five times as large.
+
+#![warn(missing_docs)]
+
+use common::params::{self, Param};
+use common::parse;
+use common::render::{self, Animation};
+use common::repl::{Command, Session};
+use common::rng::Rng;
+use common::search::{astar, Path};
+use common::{Answer, AocError, Grid, Solution};
+
+/// The full map: `map` repeated `times` in both directions, with the risk going up by
+/// one for every repetition to the right or down and wrapping from 9 back to 1.
+pub fn tile(map: &Grid<usize>, times: usize) -> Grid<usize> {
+    let (width, height) = (map.width(), map.height());
+    let mut tiled = Grid::new(width * times, height * times, 0);
+    for (x, y) in tiled.positions() {
+        let factor = x / width + y / height;
+        tiled[(x, y)] = (map[(x % width, y % height)] + factor - 1) % 9 + 1;
+    }
+    tiled
+}
+
+/// The path from the top left to the bottom right with the lowest total risk, not
+/// counting the risk of the start.
+pub fn lowest_risk_path(map: &Grid<usize>) -> Path<(usize, usize), usize> {
+    let goal = (map.width() - 1, map.height() - 1);
+
+    let (path, _) = astar(
+        (0, 0),
+        |pos| map.neighbors4(*pos).map(|n| (n, map[n])),
+        |(x, y)| (goal.0 - x) + (goal.1 - y),
+        |pos| *pos == goal,
+    );
+
+    path.expect("All positions are connected")
+}
+
+fn lowest_total_risk(map: &Grid<usize>) -> usize {
+    lowest_risk_path(map).cost
+}
+
+/// The risk levels, a digit from 1 to 9 per position.
+pub fn parse(lines: &[String]) -> Result<Grid<usize>, AocError> {
+    // Tiling wraps 9 back to 1, so there is no risk level 0
+    parse::char_grid(lines, "a risk level from 1 to 9", |c| match c.to_digit(10) {
+        Some(risk @ 1..=9) => Some(risk as usize),
+        _ => None,
+    })
+}
+
+/// Lowest total risk of a path through the map.
+pub fn star_one(map: &Grid<usize>) -> usize {
+    lowest_total_risk(map)
+}
+
+/// Lowest total risk of a path through the map tiled five times in both directions.
+pub fn star_two(map: &Grid<usize>) -> usize {
+    lowest_total_risk(&tile(map, 5))
+}
+
+/// Palette index of the cells on the path; the others are coloured by risk level
+const PATH: u8 = 10;
+
+/// The map with the path of the lowest total risk, then the map tiled `tiles` times with
+/// its path.
+pub fn render(map: &Grid<usize>, tiles: usize) -> Animation {
+    let mut palette = vec![[0, 0, 0]];
+    palette.extend(render::gradient([235, 235, 225], [40, 60, 40], 9));
+    palette.push([230, 30, 30]);
+    let mut anim = Animation::new(palette).with_delay(150);
+
+    for map in [map.clone(), tile(map, tiles)] {
+        let mut frame = map.map(|risk| *risk as u8);
+        for pos in lowest_risk_path(&map).nodes {
+            frame[pos] = PATH;
+        }
+        anim.push_frame(frame);
+    }
+    anim
+}
+
+/// A square map of `size` by `size` risk levels from 1 to 9 (at least 1 by 1).
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut out = String::with_capacity((size + 1) * size);
+    for _ in 0..size {
+        out.extend((0..size).map(|_| (b'0' + rng.range(1..10) as u8) as char));
+        out.push('\n');
+    }
+    out
+}
+
+/// The example from the puzzle description
+pub static TEST_DATA: &str = include_str!("../../examples/day15/example.txt");
+
+/// The puzzle, for the runner, with the size of the full map for part two.
+pub struct Day15 {
+    /// Times the map is repeated in both directions for part two
+    pub tiles: usize,
+}
+
+impl Default for Day15 {
+    fn default() -> Day15 {
+        Day15 { tiles: 5 }
+    }
+}
+
+static PARAMS: &[Param] = &[Param { name: "tiles", default: "5", help: "times the map is repeated in both directions for part two" }];
+
+/// The map being explored in `aoc repl`, with the safest path once it is found.
+struct Cave {
+    original: Grid<usize>,
+    map: Grid<usize>,
+    tiles: usize,
+    path: Option<Path<(usize, usize), usize>>,
+}
+
+static COMMANDS: &[Command] = &[
+    Command { name: "path", args: "", help: "finds the path with the lowest total risk" },
+    Command { name: "tile", args: "N", help: "repeats the map of the input N times in both directions" },
+];
+
+impl Session for Cave {
+    fn commands(&self) -> &'static [Command] {
+        COMMANDS
+    }
+
+    fn run(&mut self, name: &str, args: &str) -> Result<String, String> {
+        match name {
+            "path" => {
+                let path = lowest_risk_path(&self.map);
+                let found = format!("Lowest total risk {} in {} steps", path.cost, path.nodes.len() - 1);
+                self.path = Some(path);
+                Ok(format!("{}\n{}", found, self.show()))
+            }
+            "tile" => {
+                self.tiles = params::number(name, args, 1..=50)?;
+                self.map = tile(&self.original, self.tiles);
+                self.path = None;
+                Ok(self.show())
+            }
+            _ => Err(format!("unknown command '{}'", name)),
+        }
+    }
+
+    /// The risk levels; once the path is found only those on the path.
+    fn show(&self) -> String {
+        let mut chars = self.map.map(|risk| char::from_digit(*risk as u32, 10).unwrap_or('+'));
+        if let Some(path) = &self.path {
+            chars = chars.map(|_| '.');
+            for pos in &path.nodes {
+                chars[*pos] = char::from_digit(self.map[*pos] as u32, 10).unwrap_or('+');
+            }
+        }
+        format!("{}x{}, tiled {} times:\n{}", self.map.width(), self.map.height(), self.tiles, chars)
+    }
+}
+
+impl Solution for Day15 {
+    type Input = Grid<usize>;
+
+    fn example(&self) -> &'static str {
+        TEST_DATA
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Grid<usize>, AocError> {
+        parse(lines)
+    }
+
+    fn part_one(&self, map: &Grid<usize>) -> Answer {
+        star_one(map).into()
+    }
+
+    fn part_two(&self, map: &Grid<usize>) -> Answer {
+        lowest_total_risk(&tile(map, self.tiles)).into()
+    }
+
+    fn render(&self, map: &Grid<usize>) -> Option<Animation> {
+        Some(render(map, self.tiles))
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn session(&self, map: &Grid<usize>) -> Option<Box<dyn Session>> {
+        Some(Box::new(Cave { original: map.clone(), map: map.clone(), tiles: 1, path: None }))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "tiles" => self.tiles = params::number(name, value, 1..=50)?,
+            _ => return Err(format!("unknown parameter '{}'", name)),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TEST_DATA;
+
+    #[test]
+    fn test_star_one() {
+        let lines: Vec<String> = TEST_DATA
+            .lines()
+            .map(|x| x.to_string())
+            .collect();
+
+        let ans = super::star_one(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 40);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let lines: Vec<String> = TEST_DATA
+            .lines()
+            .map(|x| x.to_string())
+            .collect();
+
+        let ans = super::star_two(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 315);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let lines: Vec<String> = vec![String::from("19"), String::from("90")];
+        let err = super::parse(&lines).unwrap_err();
+        assert_eq!(err.to_string(), "2:2: expected a risk level from 1 to 9, found '0'");
+    }
+
+    #[test]
+    fn render() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let anim = super::render(&super::parse(&lines).unwrap(), 5);
+        let frames = anim.frames();
+        assert_eq!((frames[1].width(), frames[1].height()), (50, 50));
+        // The path visits every row and column at least once
+        assert!(frames.iter().all(|f| f.iter().filter(|c| **c == super::PATH).count() >= f.width() + f.height() - 1));
+        assert_eq!(frames[0][(0, 0)], super::PATH);
+        assert_eq!(frames[0][(9, 9)], super::PATH);
+    }
+
+    #[test]
+    fn generate() {
+        let input = super::generate(&mut common::rng::Rng::new(15), 30);
+        assert_eq!(input, super::generate(&mut common::rng::Rng::new(15), 30));
+
+        let lines: Vec<String> = input.lines().map(|x| x.to_string()).collect();
+        let map = super::parse(&lines).unwrap();
+        assert_eq!((map.width(), map.height()), (30, 30));
+        // Every step costs at least 1 and at most 9
+        let ans = super::star_one(&map);
+        assert!((58..=58 * 9).contains(&ans), "{}", ans);
+    }
+
+    #[test]
+    fn session() {
+        use common::Solution;
+
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+        let mut session = super::Day15::default().session(&super::parse(&lines).unwrap()).unwrap();
+
+        assert!(session.show().starts_with("10x10, tiled 1 times:\n1163751742\n"));
+        let out = session.run("path", "").unwrap();
+        assert!(out.starts_with("Lowest total risk 40 in 18 steps\n10x10, tiled 1 times:\n1.........\n1.........\n2136511...\n"), "{}", out);
+        assert!(session.run("tile", "5").unwrap().starts_with("50x50, tiled 5 times:\n11637517422274862853338597396444961841755517295286\n"));
+        assert!(session.run("path", "").unwrap().starts_with("Lowest total risk 315 "));
+        assert!(session.run("tile", "0").is_err());
+    }
+}
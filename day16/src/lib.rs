@@ -0,0 +1,609 @@
+//! Day 16: Packet Decoder. Decodes a transmission in the Buoyancy Interchange
+//! Transmission System (BITS): a hexadecimal string holding a packet, which is either
+//! a literal value or an operator with subpackets.
+//!
+//! ```
+//! let packet = day16::Packet::decode("9C0141080250320F1802104A08").unwrap();
+//! assert_eq!(packet.packet_type(), &day16::PacketType::Eq);
+//! assert_eq!(packet.value(), 1);
+//! ```
+
+#![warn(missing_docs)]
+
+use std::fmt;
+use std::str;
+use std::vec::Vec;
+use num_enum::TryFromPrimitive;
+
+use common::repl::{Command, Session};
+use common::rng::Rng;
+use common::{parse, Answer, AocError, Solution};
+
+// TODO: really use Rust's enum correctly by adding the subpackets as values in the enum.
+// This is not as easy as it appears because this enum is used as a field in the struct
+// creating a type recursion. Adding the packets as references introduces lifetime
+// constraints...
+/// The type ID of a packet, which tells what its value is.
+#[derive(Debug, Clone, Eq, PartialEq, TryFromPrimitive)]
+#[repr(u8)]
+pub enum PacketType {
+    /// Sum of the subpackets
+    Sum,
+    /// Product of the subpackets
+    Product,
+    /// Smallest subpacket
+    Min,
+    /// Largest subpacket
+    Max,
+    /// A number, stored in the packet itself
+    Literal,
+    /// 1 if the first of two subpackets is greater than the second, else 0
+    Gt,
+    /// 1 if the first of two subpackets is less than the second, else 0
+    Lt,
+    /// 1 if two subpackets are equal, else 0
+    Eq,
+    /// Only used while decoding; decoded packets never have it
+    Invalid = 255,
+}
+
+/// A decoded packet with its subpackets.
+#[derive(Debug, Clone)]
+pub struct Packet {
+    version: u8,
+    packet_type: PacketType,
+    /// The number of a literal, or the value of an operator, worked out while decoding
+    number: u64,
+    subpackets: Vec<Packet>,
+    bit_length: u32,
+}
+
+impl Packet {
+    /// Decodes the packet in a hexadecimal transmission. Expressions whose value does not
+    /// fit in 64 bits are rejected, so evaluating a decoded packet can not fail.
+    pub fn decode(hex: &str) -> Result<Packet, AocError> {
+        if hex.is_empty() {
+            return Err(AocError::new(1, 1, "", "a hexadecimal transmission"));
+        }
+        if let Some(pos) = hex.find(|c: char| !c.is_ascii_hexdigit()) {
+            return Err(AocError::at_first_char(1, hex, &hex[pos..], "a hexadecimal digit"));
+        }
+
+        let to_error = |e: PacketDecodeError, decoder: &PacketDecoder| {
+            let ofs = (decoder.cur_ofs * 2).min(hex.len());
+            AocError::at(1, hex, &hex[ofs..], e.0)
+        };
+        let mut decoder = PacketDecoder::new(hex).map_err(|e| AocError::new(1, 1, "", e.0))?;
+        let packet = parse_packet(&mut decoder).map_err(|e| to_error(e, &decoder))?;
+        decoder.clear_cache();
+        Ok(packet)
+    }
+
+    /// The version number, from 0 to 7.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// What kind of packet this is.
+    pub fn packet_type(&self) -> &PacketType {
+        &self.packet_type
+    }
+
+    /// The number of a literal packet; `None` for operators.
+    pub fn literal(&self) -> Option<u64> {
+        match self.packet_type {
+            PacketType::Literal => Some(self.number),
+            _ => None,
+        }
+    }
+
+    /// The subpackets of an operator; empty for literals.
+    pub fn subpackets(&self) -> &[Packet] {
+        &self.subpackets
+    }
+
+    /// Number of bits the packet takes up in the transmission, subpackets included.
+    pub fn bit_length(&self) -> u32 {
+        self.bit_length
+    }
+
+    /// Sum of the version numbers of this packet and all its subpackets.
+    pub fn version_sum(&self) -> usize {
+        calc_version_sum(self)
+    }
+
+    /// The value of the expression the packet stands for.
+    pub fn value(&self) -> u64 {
+        self.number
+    }
+}
+
+#[derive(Debug)]
+struct PacketDecoder {
+    hex: Vec<u8>,
+    cur_ofs: usize,
+    bin_cache: Vec<u8>,
+}
+
+/// What the decoder expected but did not find
+#[derive(Debug, Clone)]
+struct PacketDecodeError(&'static str);
+
+impl fmt::Display for PacketDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid packet, expected {}", self.0)
+    }
+}
+
+/// Deepest nesting of operator packets that is decoded, so a malicious transmission
+/// can not overflow the stack.
+const MAX_DEPTH: usize = 200;
+
+impl PacketDecoder {
+    fn new(packets: &str) -> Result<PacketDecoder, PacketDecodeError> {
+        let nibbles = packets
+            .chars()
+            .map(|c| c.to_digit(16).map(|d| d as u8).ok_or(PacketDecodeError("a hexadecimal digit")))
+            .collect::<Result<Vec<u8>, PacketDecodeError>>()?;
+        let hex = nibbles
+            .chunks(2)
+            // An odd number of digits is padded with zero bits, like the transmission itself
+            .map(|pair| pair[0] << 4 | pair.get(1).copied().unwrap_or(0))
+            // TODO: instead of converting the whole packet string at once we could store the iterator
+            .collect();
+
+        Ok(PacketDecoder {
+            hex,
+            cur_ofs: 0,
+            bin_cache: Vec::new(),
+        })
+    }
+
+    fn next_bits(&mut self, n: usize) -> Result<String, PacketDecodeError> {
+        while self.bin_cache.len() < n {
+            if self.cur_ofs >= self.hex.len() {
+                return Err(PacketDecodeError("more bits of the transmission"));
+            }
+
+            let mut bin = format!("{:08b}", self.hex[self.cur_ofs]).bytes().collect();
+            self.cur_ofs += 1;
+            self.bin_cache.append(&mut bin);
+        }
+
+        let result: String = String::from(str::from_utf8(&self.bin_cache[0..n]).unwrap());
+        self.bin_cache.drain(0..n);
+        Ok(result)
+    }
+
+    fn clear_cache(&mut self) {
+        self.bin_cache.clear();
+    }
+
+    fn get_u8(&mut self, n: usize) -> Result<u8, PacketDecodeError> {
+        // TODO: avoid the byte -> str -> byte conversion
+        u8::from_str_radix(&self.next_bits(n)?, 2).map_err(|_| PacketDecodeError("at most 8 bits"))
+    }
+
+    fn get_u32(&mut self, n: usize) -> Result<u32, PacketDecodeError> {
+        // TODO: avoid the byte -> str -> byte conversion
+        u32::from_str_radix(&self.next_bits(n)?, 2).map_err(|_| PacketDecodeError("at most 32 bits"))
+    }
+}
+
+fn parse_packet(decoder: &mut PacketDecoder) -> Result<Packet, PacketDecodeError> {
+    parse_nested_packet(decoder, 0)
+}
+
+fn parse_nested_packet(decoder: &mut PacketDecoder, depth: usize) -> Result<Packet, PacketDecodeError> {
+    if depth > MAX_DEPTH {
+        return Err(PacketDecodeError("packets nested less deeply"));
+    }
+    let mut result: Packet = Packet {
+        version: 0,
+        packet_type: PacketType::Invalid,
+        number: 0,
+        subpackets: Vec::new(),
+        bit_length: 0,
+    };
+
+    result.version = decoder.get_u8(3)?;
+    result.packet_type = PacketType::try_from(decoder.get_u8(3)?).map_err(|_| PacketDecodeError("a packet type"))?;
+    result.bit_length += 6;
+
+    match result.packet_type {
+        PacketType::Literal => {
+            let mut num: String = String::from("");
+            while decoder.get_u8(1)? == 1 {
+                num.push_str(&decoder.next_bits(4)?);
+                result.bit_length += 5;
+            }
+            num.push_str(&decoder.next_bits(4)?);
+            result.bit_length += 5;
+            result.number = u64::from_str_radix(&num, 2).map_err(|_| PacketDecodeError("a literal that fits in 64 bits"))?;
+        }
+        _ => {
+            let length_type_id = decoder.get_u8(1)?;
+            result.bit_length += 1;
+            match length_type_id {
+                0 => {
+                    let length_bits = decoder.get_u32(15)?;
+                    result.bit_length += 15;
+                    let mut bits_consumed: u32 = 0;
+                    while bits_consumed < length_bits {
+                        let packet = parse_nested_packet(decoder, depth + 1)?;
+                        bits_consumed += packet.bit_length;
+                        result.bit_length += packet.bit_length;
+                        result.subpackets.push(packet);
+                    }
+                },
+                1 => {
+                    let length_subpackets = decoder.get_u32(11)?;
+                    result.bit_length += 11;
+                    for _ in 0..length_subpackets {
+                        let packet = parse_nested_packet(decoder, depth + 1)?;
+                        result.bit_length += packet.bit_length;
+                        result.subpackets.push(packet);
+                    }
+                },
+                _ => return Err(PacketDecodeError("length type ID 0 or 1")),
+            }
+
+            match result.packet_type {
+                PacketType::Gt | PacketType::Lt | PacketType::Eq if result.subpackets.len() != 2 => {
+                    return Err(PacketDecodeError("two subpackets to compare"));
+                }
+                _ if result.subpackets.is_empty() => return Err(PacketDecodeError("at least one subpacket")),
+                _ => (),
+            }
+            result.number = evaluate(&result.packet_type, &result.subpackets)
+                .ok_or(PacketDecodeError("an expression whose value fits in 64 bits"))?;
+        }
+    }
+
+    Ok(result)
+}
+
+fn calc_version_sum(packet: &Packet) -> usize {
+    packet.subpackets.iter().map(calc_version_sum).sum::<usize>() + packet.version as usize
+}
+
+/// The value of an operator with `subpackets`, which are already evaluated; `None` if
+/// it does not fit in 64 bits.
+fn evaluate(packet_type: &PacketType, subpackets: &[Packet]) -> Option<u64> {
+    let mut values = subpackets.iter().map(|packet| packet.number);
+    match packet_type {
+        PacketType::Sum => values.try_fold(0u64, |acc, value| acc.checked_add(value)),
+        PacketType::Product => values.try_fold(1u64, |acc, value| acc.checked_mul(value)),
+        PacketType::Min => values.min(),
+        PacketType::Max => values.max(),
+        PacketType::Gt => Some((subpackets[0].number > subpackets[1].number) as u64),
+        PacketType::Lt => Some((subpackets[0].number < subpackets[1].number) as u64),
+        PacketType::Eq => Some((subpackets[0].number == subpackets[1].number) as u64),
+        PacketType::Literal | PacketType::Invalid => None,
+    }
+}
+
+/// The packet in the transmission, on a single line.
+pub fn parse(lines: &[String]) -> Result<Packet, AocError> {
+    Packet::decode(parse::single_line(lines, "a hexadecimal transmission")?)
+}
+
+/// Sum of the version numbers of all packets.
+pub fn star_one(packet: &Packet) -> usize {
+    packet.version_sum()
+}
+
+/// Value of the outermost packet.
+pub fn star_two(packet: &Packet) -> u64 {
+    packet.value()
+}
+
+/// Deepest nesting of generated operator packets.
+const MAX_GENERATED_DEPTH: usize = 8;
+
+/// A random packet of at most `budget` packets (plus the odd extra literal a comparison
+/// needs), as bits, with its version sum and value.
+fn random_packet(rng: &mut Rng, budget: &mut usize, depth: usize) -> (String, usize, u64) {
+    *budget = budget.saturating_sub(1);
+    let version = rng.range(0..8);
+    let mut bits = format!("{:03b}", version);
+
+    if *budget == 0 || depth == MAX_GENERATED_DEPTH || (depth > 0 && rng.chance(1, 4)) {
+        let groups = rng.range(1..6);
+        let number = rng.next_u64() % (1 << (4 * groups));
+        bits.push_str("100");
+        for group in (0..groups).rev() {
+            let last = if group == 0 { '0' } else { '1' };
+            bits.push_str(&format!("{}{:04b}", last, (number >> (4 * group)) & 0xf));
+        }
+        return (bits, version, number);
+    }
+
+    // The outermost packet takes as many subpackets as the budget allows
+    let (mut packet_type, count, required) = match (depth, rng.range(0..7)) {
+        (0, t) => (PacketType::try_from(t as u8 % 4).unwrap(), 2047, 1),
+        (_, t @ 0..=3) => (PacketType::try_from(t as u8).unwrap(), rng.range(1..6), 1),
+        (_, t) => (PacketType::try_from(t as u8 + 1).unwrap(), 2, 2),
+    };
+    let mut sub_bits = String::new();
+    let mut version_sum = version;
+    let mut values = Vec::new();
+    for idx in 0..count {
+        if idx >= required && *budget == 0 {
+            break;
+        }
+        let (bits, versions, value) = random_packet(rng, budget, depth + 1);
+        sub_bits.push_str(&bits);
+        version_sum += versions;
+        values.push(value);
+    }
+
+    // Products that overflow become sums, and sums that overflow become maximums
+    let product = values.iter().try_fold(1u64, |acc, v| acc.checked_mul(*v));
+    let sum = values.iter().try_fold(0u64, |acc, v| acc.checked_add(*v));
+    if packet_type == PacketType::Product && product.is_none() {
+        packet_type = PacketType::Sum;
+    }
+    if packet_type == PacketType::Sum && sum.is_none() {
+        packet_type = PacketType::Max;
+    }
+    let value = match packet_type {
+        PacketType::Sum => sum.unwrap(),
+        PacketType::Product => product.unwrap(),
+        PacketType::Min => *values.iter().min().unwrap(),
+        PacketType::Max => *values.iter().max().unwrap(),
+        PacketType::Gt => (values[0] > values[1]) as u64,
+        PacketType::Lt => (values[0] < values[1]) as u64,
+        PacketType::Eq => (values[0] == values[1]) as u64,
+        PacketType::Literal | PacketType::Invalid => unreachable!(),
+    };
+
+    bits.push_str(&format!("{:03b}", packet_type as u8));
+    if sub_bits.len() < 1 << 15 && rng.chance(1, 2) {
+        bits.push_str(&format!("0{:015b}", sub_bits.len()));
+    } else {
+        bits.push_str(&format!("1{:011b}", values.len()));
+    }
+    bits.push_str(&sub_bits);
+    (bits, version_sum, value)
+}
+
+/// The bits as hexadecimal digits, padded with zero bits to a whole digit.
+fn to_hex(bits: &str) -> String {
+    let mut bits = bits.to_string();
+    while !bits.len().is_multiple_of(4) {
+        bits.push('0');
+    }
+    bits.as_bytes()
+        .chunks(4)
+        .map(|nibble| {
+            let digit = nibble.iter().fold(0, |acc, bit| acc << 1 | (bit - b'0') as u32);
+            std::char::from_digit(digit, 16).unwrap().to_ascii_uppercase()
+        })
+        .collect()
+}
+
+/// A transmission of about `size` packets, whose expression can be evaluated without
+/// overflowing.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut budget = size.max(1);
+    let (bits, _, _) = random_packet(rng, &mut budget, 0);
+    to_hex(&bits) + "\n"
+}
+
+/// The last decoded packet in `aoc repl`, at first the one of the input.
+struct Decoding {
+    packet: Packet,
+}
+
+static COMMANDS: &[Command] = &[Command { name: "decode", args: "HEX", help: "decodes a transmission" }];
+
+/// Adds a line per packet to `out`, with the subpackets indented below their operator.
+fn describe_packet(packet: &Packet, depth: usize, out: &mut String) {
+    let indent = "  ".repeat(depth);
+    match packet.literal() {
+        Some(number) => {
+            *out += &format!("{}version {}: literal {}, {} bits\n", indent, packet.version, number, packet.bit_length)
+        }
+        None => *out += &format!(
+            "{}version {}: {:?} = {}, {} bits\n",
+            indent,
+            packet.version,
+            packet.packet_type,
+            packet.value(),
+            packet.bit_length
+        ),
+    }
+    for sub in &packet.subpackets {
+        describe_packet(sub, depth + 1, out);
+    }
+}
+
+impl Session for Decoding {
+    fn commands(&self) -> &'static [Command] {
+        COMMANDS
+    }
+
+    fn run(&mut self, name: &str, args: &str) -> Result<String, String> {
+        match name {
+            "decode" => {
+                self.packet = Packet::decode(args).map_err(|e| e.to_string())?;
+                Ok(self.show())
+            }
+            _ => Err(format!("unknown command '{}'", name)),
+        }
+    }
+
+    fn show(&self) -> String {
+        let mut out = format!("Version sum {}, value {}\n", self.packet.version_sum(), self.packet.value());
+        describe_packet(&self.packet, 0, &mut out);
+        out
+    }
+}
+
+/// One of the examples from the puzzle description
+pub static TEST_DATA: &str = include_str!("../../examples/day16/example.txt");
+
+/// The puzzle, for the runner.
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Packet;
+
+    fn example(&self) -> &'static str {
+        TEST_DATA
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Packet, AocError> {
+        parse(lines)
+    }
+
+    fn part_one(&self, packet: &Packet) -> Answer {
+        star_one(packet).into()
+    }
+
+    fn part_two(&self, packet: &Packet) -> Answer {
+        star_two(packet).into()
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn session(&self, packet: &Packet) -> Option<Box<dyn Session>> {
+        Some(Box::new(Decoding { packet: packet.clone() }))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::TEST_DATA;
+
+    static NUMBER_TEST_PACKET: &str = "D2FE28";
+
+    #[test]
+    fn decode_number() {
+        let mut decoder = super::PacketDecoder::new(NUMBER_TEST_PACKET).unwrap();
+        assert_eq!(decoder.get_u8(3).unwrap(), 6);
+        assert_eq!(decoder.get_u8(3).unwrap(), 4);
+        assert_eq!(decoder.get_u8(5).unwrap(), 23);
+        assert_eq!(decoder.get_u8(5).unwrap(), 30);
+        assert_eq!(decoder.get_u8(5).unwrap(), 5);
+    }
+
+    #[test]
+    fn parse_type_4() {
+        let mut decoder = super::PacketDecoder::new(NUMBER_TEST_PACKET).unwrap();
+        let packet = super::parse_packet(&mut decoder).unwrap();
+        assert_eq!(packet.version, 6);
+        assert_eq!(packet.packet_type, super::PacketType::Literal);
+        assert_eq!(packet.number, 2021);
+    }
+
+    static OPERATOR_TEST_PACKET: &str = "38006F45291200";
+
+    #[test]
+    fn parse_operator() {
+        let mut decoder = super::PacketDecoder::new(OPERATOR_TEST_PACKET).unwrap();
+        let packet = super::parse_packet(&mut decoder).unwrap();
+        assert_eq!(packet.version, 1);
+        assert_eq!(packet.packet_type, super::PacketType::Lt);
+        assert_eq!(packet.subpackets.len(), 2);
+        assert_eq!(packet.subpackets[0].packet_type, super::PacketType::Literal);
+        assert_eq!(packet.subpackets[0].number, 10);
+        assert_eq!(packet.subpackets[1].packet_type, super::PacketType::Literal);
+        assert_eq!(packet.subpackets[1].number, 20);
+    }
+    
+    static OTHER_OPERATOR_TEST_PACKET: &str = "EE00D40C823060";
+
+    #[test]
+    fn parse_other_operator() {
+        let mut decoder = super::PacketDecoder::new(OTHER_OPERATOR_TEST_PACKET).unwrap();
+        let packet = super::parse_packet(&mut decoder).unwrap();
+        assert_eq!(packet.version, 7);
+        assert_eq!(packet.packet_type, super::PacketType::Max);
+        assert_eq!(packet.subpackets.len(), 3);
+        assert_eq!(packet.subpackets[0].packet_type, super::PacketType::Literal);
+        assert_eq!(packet.subpackets[0].number, 1);
+        assert_eq!(packet.subpackets[1].packet_type, super::PacketType::Literal);
+        assert_eq!(packet.subpackets[1].number, 2);
+        assert_eq!(packet.subpackets[2].packet_type, super::PacketType::Literal);
+        assert_eq!(packet.subpackets[2].number, 3);
+    }
+
+    static NESTED_OPERATOR_TEST_PACKET: &str = "8A004A801A8002F478";
+
+    #[test]
+    fn parse_nested_operator() {
+        let mut decoder = super::PacketDecoder::new(NESTED_OPERATOR_TEST_PACKET).unwrap();
+        let packet = super::parse_packet(&mut decoder).unwrap();
+        assert_eq!(packet.version, 4);
+        assert_eq!(packet.subpackets.len(), 1);
+        assert_eq!(packet.subpackets[0].version, 1);
+        assert_eq!(packet.subpackets[0].subpackets.len(), 1);
+        assert_eq!(packet.subpackets[0].subpackets[0].version, 5);
+        assert_eq!(packet.subpackets[0].subpackets[0].subpackets.len(), 1);
+        assert_eq!(packet.subpackets[0].subpackets[0].subpackets[0].version, 6);
+        assert_eq!(super::calc_version_sum(&packet), 16);
+    }
+
+    #[test]
+    fn test_star_one() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let ans = super::star_one(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 31);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let lines = |line: &str| vec![line.to_string()];
+        let err = super::parse(&lines("D2FE")).unwrap_err();
+        assert_eq!(err.to_string(), "1:5: expected more bits of the transmission");
+        // Sum and product of two literals of 2^64 - 1
+        let err = super::parse(&lines("020084FFFFFFFFFFFFFFFFFFEF13FFFFFFFFFFFFFFFFFFBC")).unwrap_err();
+        assert_eq!(err.to_string(), "1:49: expected an expression whose value fits in 64 bits");
+        assert!(super::parse(&lines("060084FFFFFFFFFFFFFFFFFFEF13FFFFFFFFFFFFFFFFFFBC")).is_err());
+        let err = super::parse(&lines("D2FE2G")).unwrap_err();
+        assert_eq!(err.to_string(), "1:6: expected a hexadecimal digit, found 'G'");
+        // Less-than operator with a single subpacket
+        let err = super::parse(&lines("3A004450")).unwrap_err();
+        assert_eq!(err.to_string(), "1:9: expected two subpackets to compare");
+        assert!(super::PacketDecoder::new("Dé").is_err());
+
+        let err = super::parse(&[String::from("D2FE28"), String::from("D2FE28")]).unwrap_err();
+        assert_eq!(err.to_string(), "2:1: expected end of input, found 'D2FE28'");
+        let err = super::parse(&[]).unwrap_err();
+        assert_eq!(err.to_string(), "1:1: expected a hexadecimal transmission");
+    }
+
+    #[test]
+    fn generate() {
+        let input = super::generate(&mut common::rng::Rng::new(16), 500);
+        assert_eq!(input, super::generate(&mut common::rng::Rng::new(16), 500));
+        assert!(super::parse(&[input.trim_end().to_string()]).is_ok());
+
+        for seed in 0..50 {
+            let mut rng = common::rng::Rng::new(seed);
+            let (bits, version_sum, value) = super::random_packet(&mut rng, &mut (seed as usize * 10), 0);
+            let packet = super::parse(&[super::to_hex(&bits)]).unwrap();
+            assert_eq!(super::star_one(&packet), version_sum);
+            assert_eq!(super::star_two(&packet), value);
+        }
+    }
+
+    #[test]
+    fn session() {
+        use common::Solution;
+
+        let mut session = super::Day16.session(&super::Packet::decode(NUMBER_TEST_PACKET).unwrap()).unwrap();
+        assert_eq!(session.show(), "Version sum 6, value 2021\nversion 6: literal 2021, 21 bits\n");
+        assert_eq!(
+            session.run("decode", "38006F45291200").unwrap(),
+            "Version sum 9, value 1\nversion 1: Lt = 1, 49 bits\n  version 6: literal 10, 11 bits\n  version 2: literal 20, 16 bits\n"
+        );
+        assert_eq!(session.run("decode", "D2FE2G"), Err(String::from("1:6: expected a hexadecimal digit, found 'G'")));
+    }
+}
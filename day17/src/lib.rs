@@ -0,0 +1,387 @@
+//! Day 17: Trick Shot. Finds the launch velocities that make a probe end up in a
+//! target area, the probe slowing down from drag and falling from gravity.
+
+#![warn(missing_docs)]
+
+use std::collections::HashSet;
+
+use common::params::{self, Param};
+use common::visual::{self, Frame};
+use common::{parse, Answer, AocError, Grid, Solution};
+
+struct Probe {
+    x: i32,
+    y: i32,
+
+    xvel: i32,
+    yvel: i32,
+    
+    maxy: i32,
+}
+
+/// The target area, both ranges inclusive.
+#[derive(Debug, Clone)]
+pub struct Target {
+    /// Lowest x coordinate
+    pub xmin: i32,
+    /// Highest x coordinate
+    pub xmax: i32,
+    /// Lowest y coordinate
+    pub ymin: i32,
+    /// Highest y coordinate
+    pub ymax: i32,
+}
+
+impl Probe {
+    fn new() -> Probe {
+        Probe { x: 0, y: 0, xvel: 0, yvel: 0, maxy: 0 }
+    }
+
+    fn step(&mut self) {
+        self.x += self.xvel;
+        self.y += self.yvel;
+
+        if self.y > self.maxy {
+            self.maxy = self.y;
+        }
+
+        if self.xvel > 0 {
+            self.xvel -= 1;
+        }
+        if self.xvel < 0 {
+            self.xvel += 1;
+        }
+        self.yvel -= 1;
+    }
+
+    fn is_in_target(&self, target: &Target) -> bool {
+        self.x >= target.xmin && self.x <= target.xmax && self.y >= target.ymin && self.y <= target.ymax 
+    }
+
+    /// Below the target and no longer going up, also when the target is above the start
+    fn can_never_reach_target(&self, target: &Target) -> bool {
+        self.y < target.ymin && self.yvel <= 0
+    }
+}
+
+/// Targets further out would make the probe overflow on its way there.
+const MAX_COORDINATE: i32 = 100_000;
+
+fn coordinate(line: &str, text: &str, what: &str) -> Result<i32, AocError> {
+    match parse::number::<i32>(1, line, text, what)? {
+        coord if (-MAX_COORDINATE..=MAX_COORDINATE).contains(&coord) => Ok(coord),
+        _ => Err(AocError::at(1, line, text, &format!("{} from -{} to {}", what, MAX_COORDINATE, MAX_COORDINATE))),
+    }
+}
+
+fn parse_range(line: &str, range: &str, axis: &str) -> Result<(i32, i32), AocError> {
+    let (min, max) = parse::split_pair(1, line, range, "..", &format!("'..' in the {} range", axis))?;
+    let min = coordinate(line, min, &format!("minimum {}", axis))?;
+    let max = coordinate(line, max, &format!("maximum {}", axis))?;
+    if min > max {
+        return Err(AocError::at(1, line, range, &format!("{} range from low to high", axis)));
+    }
+
+    Ok((min, max))
+}
+
+/// The target area from a line like `target area: x=20..30, y=-10..-5`.
+pub fn parse(lines: &[String]) -> Result<Target, AocError> {
+    // target area: x=153..199, y=-114..-75
+    let line = parse::single_line(lines, "'target area: '")?;
+    let ranges = line
+        .strip_prefix("target area: ")
+        .ok_or_else(|| AocError::at(1, line, line, "'target area: '"))?;
+    let (xrange, yrange) = ranges
+        .split_once(", ")
+        .ok_or_else(|| AocError::at_end(1, line, "', ' between the ranges"))?;
+    let xrange = xrange
+        .strip_prefix("x=")
+        .ok_or_else(|| AocError::at(1, line, xrange, "'x='"))?;
+    let yrange = yrange
+        .strip_prefix("y=")
+        .ok_or_else(|| AocError::at(1, line, yrange, "'y='"))?;
+    let (xmin, xmax) = parse_range(line, xrange, "x")?;
+    let (ymin, ymax) = parse_range(line, yrange, "y")?;
+
+    Ok(Target { xmin, xmax, ymin, ymax })
+}
+
+/// Steps a probe launched with the given velocity until it is in the target or can
+/// never reach it any more. The start does not count, even if it is in the target.
+fn launch(target: &Target, xvel: i32, yvel: i32) -> Probe {
+    let mut probe = Probe::new();
+    probe.yvel = yvel;
+    probe.xvel = xvel;
+
+    probe.step();
+    while !probe.is_in_target(target) && !probe.can_never_reach_target(target) {
+        probe.step();
+    }
+
+    probe
+}
+
+/// Launch velocities from `-MAX_VELOCITY` up to `MAX_VELOCITY` are tried in both
+/// directions unless another maximum is given.
+pub const MAX_VELOCITY: i32 = 200;
+
+/// The launch velocity that reaches the target and goes highest, if any, trying
+/// velocities up to `max_velocity` in both directions.
+pub fn highest_shot(target: &Target, max_velocity: i32) -> Option<(i32, i32)> {
+    let mut best: Option<(i32, i32)> = None;
+    for yvel in -max_velocity..=max_velocity {
+        for xvel in -max_velocity..=max_velocity {
+            if launch(target, xvel, yvel).is_in_target(target) {
+                best = Some((xvel, yvel));
+            }
+        }
+    }
+
+    best
+}
+
+/// Highest y position a probe that reaches the target can get to.
+pub fn star_one(target: &Target) -> i32 {
+    highest_point(target, MAX_VELOCITY)
+}
+
+/// Like `star_one`, trying velocities up to `max_velocity`.
+pub fn highest_point(target: &Target, max_velocity: i32) -> i32 {
+    match highest_shot(target, max_velocity) {
+        Some((xvel, yvel)) => {
+            // A target above the start may be reached on the way up, before the top
+            let mut probe = launch(target, xvel, yvel);
+            while probe.yvel > 0 {
+                probe.step();
+            }
+            probe.maxy
+        }
+        None => 0,
+    }
+}
+
+/// Number of launch velocities that make the probe reach the target.
+pub fn star_two(target: &Target) -> usize {
+    count_shots(target, MAX_VELOCITY)
+}
+
+/// Like `star_two`, trying velocities up to `max_velocity`.
+pub fn count_shots(target: &Target, max_velocity: i32) -> usize {
+    let mut probes: HashSet<(i32,i32)> = HashSet::new();
+    for yvel in -max_velocity..=max_velocity {
+        for xvel in -max_velocity..=max_velocity {
+            let probe = launch(target, xvel, yvel);
+
+            if probe.is_in_target(target) {
+                probes.insert((yvel, xvel));
+            }
+        }
+    }
+
+    probes.len()
+}
+
+/// Size of the view of the trajectory in the terminal, in characters
+const COLUMNS: usize = 100;
+const ROWS: usize = 40;
+
+/// The positions of a probe so far and the target, scaled to fit the terminal.
+fn trajectory_text(target: &Target, trajectory: &[(i32, i32)]) -> String {
+    let x0 = trajectory.iter().map(|p| p.0).min().unwrap().min(target.xmin);
+    let x1 = trajectory.iter().map(|p| p.0).max().unwrap().max(target.xmax);
+    let y0 = trajectory.iter().map(|p| p.1).min().unwrap().min(target.ymin);
+    let y1 = trajectory.iter().map(|p| p.1).max().unwrap().max(target.ymax);
+    let column = |x: i32| ((x - x0) as i64 * (COLUMNS - 1) as i64 / (x1 - x0).max(1) as i64) as usize;
+    let row = |y: i32| ((y1 - y) as i64 * (ROWS - 1) as i64 / (y1 - y0).max(1) as i64) as usize;
+
+    let mut cells: Grid<(char, u8)> = Grid::new(COLUMNS, ROWS, (' ', 0));
+    for y in row(target.ymax)..=row(target.ymin) {
+        for x in column(target.xmin)..=column(target.xmax) {
+            cells[(x, y)] = ('░', 33);
+        }
+    }
+    for (x, y) in trajectory {
+        cells[(column(*x), row(*y))] = ('#', 226);
+    }
+    cells[(column(0), row(0))] = ('S', 46);
+    if let Some((x, y)) = trajectory.last() {
+        cells[(column(*x), row(*y))] = ('@', 196);
+    }
+    visual::grid_text(&cells, |cell| *cell)
+}
+
+/// Every step of the probe of part one, from the launch until it is in the target.
+pub fn visualize(target: &Target, max_velocity: i32) -> Vec<Frame> {
+    let (xvel, yvel) = match highest_shot(target, max_velocity) {
+        Some(velocity) => velocity,
+        None => return vec![Frame::new("No launch velocity reaches the target", trajectory_text(target, &[(0, 0)]))],
+    };
+
+    let mut probe = Probe::new();
+    probe.xvel = xvel;
+    probe.yvel = yvel;
+    let mut trajectory = vec![(0, 0)];
+    let mut frames = Vec::new();
+    loop {
+        let caption = format!(
+            "Step {}: launched with ({},{}), at ({},{}) with velocity ({},{})",
+            trajectory.len() - 1, xvel, yvel, probe.x, probe.y, probe.xvel, probe.yvel
+        );
+        frames.push(Frame::new(caption, trajectory_text(target, &trajectory)));
+        let launched = trajectory.len() > 1;
+        if launched && (probe.is_in_target(target) || probe.can_never_reach_target(target)) {
+            break;
+        }
+        probe.step();
+        trajectory.push((probe.x, probe.y));
+    }
+    frames
+}
+
+/// The example from the puzzle description
+pub static TEST_DATA: &str = include_str!("../../examples/day17/example.txt");
+
+/// The puzzle, for the runner, with the launch velocities to try.
+pub struct Day17 {
+    /// Highest launch velocity tried in either direction
+    pub max_velocity: i32,
+    /// Target to aim for instead of the one in the input
+    pub target: Option<Target>,
+}
+
+impl Default for Day17 {
+    fn default() -> Day17 {
+        Day17 { max_velocity: MAX_VELOCITY, target: None }
+    }
+}
+
+static PARAMS: &[Param] = &[
+    Param { name: "velocity", default: "200", help: "highest launch velocity tried in either direction" },
+    Param { name: "target", default: "input", help: "target area like 'x=20..30, y=-10..-5' instead of the input" },
+];
+
+impl Solution for Day17 {
+    type Input = Target;
+
+    fn example(&self) -> &'static str {
+        TEST_DATA
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Target, AocError> {
+        match &self.target {
+            Some(target) => Ok(target.clone()),
+            None => parse(lines),
+        }
+    }
+
+    fn part_one(&self, target: &Target) -> Answer {
+        highest_point(target, self.max_velocity).into()
+    }
+
+    fn part_two(&self, target: &Target) -> Answer {
+        count_shots(target, self.max_velocity).into()
+    }
+
+    fn visualize(&self, target: &Target) -> Option<Vec<Frame>> {
+        Some(visualize(target, self.max_velocity))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "velocity" => self.max_velocity = params::number(name, value, 1..=2000)?,
+            "target" if value == "input" => self.target = None,
+            "target" => {
+                let line = format!("target area: {}", value);
+                let target = parse(&[line]).map_err(|e| format!("invalid value '{}' for target: {}", value, e))?;
+                self.target = Some(target);
+            }
+            _ => return Err(format!("unknown parameter '{}'", name)),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TEST_DATA;
+
+    #[test]
+    fn parse() {
+        let lines = vec![String::from(TEST_DATA.trim_end())];
+        let target = super::parse(&lines).unwrap();
+        assert_eq!((target.xmin, target.xmax, target.ymin, target.ymax), (20, 30, -10, -5));
+
+        let lines = vec![String::from("target area: x=20..30, y=-10...-5")];
+        let err = super::parse(&lines).unwrap_err();
+        assert_eq!(err.to_string(), "1:31: expected maximum y, found '.-5'");
+
+        let lines = vec![String::from("target area: x=20..30, y=-2147483648..-2147483000")];
+        let err = super::parse(&lines).unwrap_err();
+        assert_eq!(err.expected, "minimum y from -100000 to 100000");
+    }
+
+    #[test]
+    fn test_star_one() {
+        let lines = vec![String::from(TEST_DATA.trim_end())];
+
+        let ans = super::star_one(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 45);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let lines = vec![String::from(TEST_DATA.trim_end())];
+
+        let ans = super::star_two(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 112);
+    }
+
+    #[test]
+    fn visualize() {
+        let lines = vec![String::from(TEST_DATA.trim_end())];
+
+        let frames = super::visualize(&super::parse(&lines).unwrap(), super::MAX_VELOCITY);
+        // Launched with (7,9), the probe is at the top after 9 steps and in the target after 20
+        assert_eq!(frames.len(), 21);
+        assert_eq!(frames[9].caption, "Step 9: launched with (7,9), at (28,45) with velocity (0,0)");
+        assert!(frames[20].text.contains('@'));
+        assert_eq!(frames[20].text.lines().count(), super::ROWS);
+    }
+
+    #[test]
+    fn params() {
+        use common::{Puzzle, Solution};
+
+        let mut day = super::Day17::default();
+        day.set_param("target", "x=20..30, y=-10..-5").unwrap();
+        let target = day.parse_input(&[String::from("target area: x=1..2, y=-2..-1")]).unwrap();
+        assert_eq!(day.solve(&*target, common::Part::Two), common::Answer::Number(112));
+
+        // The highest shot goes up at 9, which is not tried any more
+        day.set_param("velocity", "8").unwrap();
+        assert_eq!(day.solve(&*target, common::Part::One), common::Answer::Number(36));
+
+        let err = day.set_param("target", "x=20..30").unwrap_err();
+        assert_eq!(err, "invalid value 'x=20..30' for target: 1:22: expected ', ' between the ranges");
+        assert!(day.set_param("velocity", "0").is_err());
+    }
+
+    #[test]
+    fn target_not_below_start() {
+        let target = |line: &str| super::parse(&[String::from(line)]).unwrap();
+
+        // Launched with (6,4), the probe is at (20,9) after 5 steps
+        let above = target("target area: x=20..30, y=5..10");
+        assert!(super::launch(&above, 6, 4).is_in_target(&above));
+        assert_eq!(super::star_one(&above), 10 * 11 / 2);
+
+        // Only the velocities that come back to the start count, (0,0) to (0,3)
+        let start = target("target area: x=0..0, y=0..0");
+        assert_eq!(super::count_shots(&start, 3), 4);
+    }
+}
@@ -0,0 +1,550 @@
+//! Day 18: Snailfish. Does the homework of the snailfish: adding up numbers that are
+//! pairs of numbers, reducing every sum by exploding and splitting pairs.
+//!
+//! Snailfish numbers are kept as the symbols they are written with:
+//!
+//! ```
+//! let left = day18::parse_number("[[[[4,3],4],4],[7,[[8,4],9]]]").unwrap();
+//! let right = day18::parse_number("[1,1]").unwrap();
+//! let sum = day18::reduce(&day18::add(&left, &right));
+//! assert_eq!(day18::to_string(&sum), "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
+//! assert_eq!(day18::magnitude(&sum), 1384);
+//! ```
+
+#![warn(missing_docs)]
+
+use std::vec::Vec;
+
+use common::repl::{Command, Session};
+use common::rng::Rng;
+use common::{Answer, AocError, Solution};
+
+/// A symbol of a written snailfish number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symbol {
+    /// `[`, the start of a pair
+    LeftParen,
+    /// A regular number; 10 and up only occur while reducing
+    Number(u32),
+    /// `,` between the elements of a pair
+    Comma,
+    /// `]`, the end of a pair
+    RightParen,
+}
+
+/// Checks the element of a snailfish number starting at `pos`, a regular number or a
+/// pair at nesting level `depth`; returns where it ends, or where it goes wrong and
+/// what was expected there.
+fn check_element(num: &[Symbol], pos: usize, depth: usize, max_depth: usize) -> Result<usize, (usize, &'static str)> {
+    match num.get(pos) {
+        Some(Symbol::Number(_)) => Ok(pos + 1),
+        Some(Symbol::LeftParen) if depth < max_depth => {
+            let pos = check_element(num, pos + 1, depth + 1, max_depth)?;
+            if num.get(pos) != Some(&Symbol::Comma) {
+                return Err((pos, "','"));
+            }
+            let pos = check_element(num, pos + 1, depth + 1, max_depth)?;
+            if num.get(pos) != Some(&Symbol::RightParen) {
+                return Err((pos, "']'"));
+            }
+            Ok(pos + 1)
+        }
+        Some(Symbol::LeftParen) => Err((pos, "a regular number, pairs are nested too deep")),
+        _ => Err((pos, "'[' or a digit")),
+    }
+}
+
+/// Parses a snailfish number with pairs nested at most `max_depth` deep.
+fn parse_simple(line_nr: usize, line: &str, max_depth: usize) -> Result<Vec<Symbol>, AocError> {
+    let mut result: Vec<Symbol> = Vec::new();
+
+    for (pos, ch) in line.char_indices() {
+        result.push(match ch {
+            '[' => Symbol::LeftParen,
+            '0'..='9' => Symbol::Number(ch.to_digit(10).unwrap()),
+            ',' => Symbol::Comma,
+            ']' => Symbol::RightParen,
+            _ => return Err(AocError::at_first_char(line_nr, line, &line[pos..], "'[', ']', ',' or a digit")),
+        });
+    }
+
+    // Every symbol is a single ASCII character, so symbol indices are byte offsets
+    let end = match result.first() {
+        Some(Symbol::LeftParen) => check_element(&result, 0, 0, max_depth),
+        _ => Err((0, "'['")),
+    };
+    match end {
+        Ok(end) if end == result.len() => Ok(result),
+        Ok(end) => Err(AocError::at_first_char(line_nr, line, &line[end..], "end of line")),
+        Err((pos, expected)) => Err(AocError::at_first_char(line_nr, line, &line[pos..], expected)),
+    }
+}
+
+fn explode(num: &[Symbol]) -> (Vec<Symbol>, bool) {
+    let mut result: Vec<Symbol> = Vec::new();
+    let mut curdepth: u32 = 0;
+    let mut prev_idx: Option<usize> = None;
+    let mut right_num: Option<u32> = None;
+    let mut iter = num.iter().enumerate();
+    let mut exploded: bool = false;
+    while let Some((idx, sym)) = iter.next() {
+        match sym {
+            Symbol::LeftParen => {
+                curdepth += 1;
+                if curdepth == 5 && !exploded {
+                    // Explode
+                    match iter.next() {
+                        Some((_, Symbol::Number(val))) => {
+                            if let Some(prev_idx) = prev_idx {
+                                if let Symbol::Number(prev) = result[prev_idx] {
+                                    result[prev_idx] = Symbol::Number(prev + val);
+                                }
+                            }
+                        }
+                        _v => panic!("invalid left side when exploding: {:?}", _v)
+                    }
+                    if let Some((_, Symbol::Comma)) = iter.next() {
+                    } else {
+                        panic!("expected comma when exploding");
+                    }
+                    match iter.next() {
+                        Some((_, Symbol::Number(val))) => {
+                            right_num = Some(*val);
+                        }
+                        _ => panic!("invalid right side when exploding")
+                    }
+                    if let Some((_, Symbol::RightParen)) = iter.next() {
+                    } else {
+                        panic!("expected right parenthesis when exploding");
+                    }
+                    result.push(Symbol::Number(0));
+                    exploded = true;
+                } else {
+                    result.push(*sym);
+                }
+            },
+            Symbol::RightParen => {
+                result.push(*sym);
+                curdepth -= 1;
+            }
+            Symbol::Number(val) => {
+                prev_idx = Some(idx);
+                if right_num.is_some() {
+                    result.push(Symbol::Number(val + right_num.unwrap()));
+                    right_num = None;
+                } else {
+                    result.push(*sym);
+                }
+            },
+            Symbol::Comma => result.push(*sym),
+        }
+    }
+
+    (result, exploded)
+}
+
+fn split(num: &[Symbol]) -> (Vec<Symbol>, bool) {
+    let mut result: Vec<Symbol> = Vec::new();
+    let mut splitted: bool = false;
+
+    for sym in num.iter() {
+        match sym {
+            Symbol::Number(val) => {
+                if *val < 10 || splitted {
+                    result.push(*sym);
+                } else {
+                    result.push(Symbol::LeftParen);
+                    result.push(Symbol::Number(*val / 2));
+                    result.push(Symbol::Comma);
+                    result.push(Symbol::Number(val - (*val / 2)));
+                    result.push(Symbol::RightParen);
+                    splitted = true;
+                }
+            },
+            _ => result.push(*sym),
+        }
+    }
+
+    (result, splitted)
+}
+
+/// The pair of `left` and `right`, which still needs to be reduced.
+pub fn add(left: &[Symbol], right: &[Symbol]) -> Vec<Symbol> {
+    let mut result: Vec<Symbol> = Vec::new();
+    result.push(Symbol::LeftParen);
+    result.extend_from_slice(left);
+    result.push(Symbol::Comma);
+    result.extend_from_slice(right);
+    result.push(Symbol::RightParen);
+    result
+}
+
+/// Reduces a number: explodes the leftmost pair nested inside four pairs, or else splits
+/// the leftmost regular number of 10 or more, until neither is possible.
+pub fn reduce(num: &[Symbol]) -> Vec<Symbol> {
+    let mut result: Vec<Symbol> = num.to_vec();
+
+    loop
+    {
+        loop
+        {
+            let res = explode(&result);
+            if !res.1 {
+                break;
+            }
+            result = res.0;
+        }
+        let res = split(&result);
+        if !res.1 {
+            break;
+        }
+        result = res.0;
+    }
+
+    result
+}
+
+fn intern_mag(iter: &mut std::slice::Iter<Symbol>) -> u32 {
+    match iter.next() {
+        Some(Symbol::LeftParen) => {},
+        Some(Symbol::Number(val)) => return *val,
+        _ => panic!("Expected number or left parenthesis in magnitude"),
+    }
+
+    let left_mag = intern_mag(iter);
+    if let Some(Symbol::Comma) = iter.next() {
+    } else {
+        panic!("Expected comma in magnitude");
+    }
+    let right_mag = intern_mag(iter);
+    if let Some(Symbol::RightParen) = iter.next() {
+    } else {
+        panic!("Expected right parenthesis in magnitude");
+    }
+
+    left_mag * 3 + right_mag * 2
+}
+
+/// Three times the magnitude of the left element of a pair plus twice that of the right
+/// one; the magnitude of a regular number is the number itself.
+pub fn magnitude(num: &[Symbol]) -> u32 {
+    let mut iter = num.iter();
+    intern_mag(&mut iter)
+}
+
+/// The numbers in the homework are reduced, so their pairs are nested at most four deep
+const MAX_INPUT_DEPTH: usize = 4;
+
+/// A reduced snailfish number, like the ones in the homework.
+pub fn parse_number(line: &str) -> Result<Vec<Symbol>, AocError> {
+    parse_simple(1, line, MAX_INPUT_DEPTH)
+}
+
+/// The snailfish numbers of the homework, one per line.
+pub fn parse(lines: &[String]) -> Result<Vec<Vec<Symbol>>, AocError> {
+    if lines.is_empty() {
+        return Err(AocError::new(1, 1, "", "a snailfish number"));
+    }
+    lines
+        .iter()
+        .enumerate()
+        .map(|(idx, l)| parse_simple(idx + 1, l, MAX_INPUT_DEPTH))
+        .collect()
+}
+
+/// Magnitude of the sum of all numbers.
+pub fn star_one(nums: &[Vec<Symbol>]) -> u32 {
+    let mut iter = nums.iter();
+    let mut num = iter.next().unwrap().clone();
+    for right in iter {
+        num = add(&num, right);
+        num = reduce(&num);
+    }
+
+    magnitude(&num)
+}
+
+/// Largest magnitude of the sum of two different numbers.
+pub fn star_two(nums: &[Vec<Symbol>]) -> u32 {
+    let mut magns: Vec<Vec<u32>> = Vec::new();
+
+    for y in 0..nums.len() {
+        magns.push(Vec::new());
+        for x in 0..nums.len() {
+            if x == y {
+                magns[y].push(0);
+                continue;
+            }
+
+            magns[y].push(magnitude(&reduce(&add(&nums[y], &nums[x]))));
+        }
+    }
+
+    *magns.iter().map(|xvec| xvec.iter().max().unwrap()).max().unwrap()
+}
+
+/// A random reduced snailfish number whose outer pair is at `depth`; each side is a
+/// nested pair with a chance of `nesting` in 100.
+fn random_number(rng: &mut Rng, depth: usize, nesting: usize) -> Vec<Symbol> {
+    let side = |rng: &mut Rng| {
+        if depth < MAX_INPUT_DEPTH && rng.range(0..100) < nesting {
+            random_number(rng, depth + 1, nesting)
+        } else {
+            vec![Symbol::Number(rng.range(0..10) as u32)]
+        }
+    };
+    let left = side(rng);
+    let right = side(rng);
+    add(&left, &right)
+}
+
+/// The number as it is written.
+pub fn to_string(num: &[Symbol]) -> String {
+    num.iter()
+        .map(|sym| match sym {
+            Symbol::LeftParen => String::from("["),
+            Symbol::Number(val) => val.to_string(),
+            Symbol::Comma => String::from(","),
+            Symbol::RightParen => String::from("]"),
+        })
+        .collect()
+}
+
+/// Homework of `size` reduced snailfish numbers (at least two).
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(2)).map(|_| to_string(&random_number(rng, 1, 70)) + "\n").collect()
+}
+
+/// The example from the puzzle description
+pub static TEST_DATA: &str = include_str!("../../examples/day18/example.txt");
+
+/// The running sum of `aoc repl`, with the numbers of the homework not added yet.
+struct Homework {
+    nums: Vec<Vec<Symbol>>,
+    next: usize,
+    sum: Option<Vec<Symbol>>,
+    added: usize,
+}
+
+static COMMANDS: &[Command] = &[
+    Command { name: "add", args: "NUMBER", help: "adds a snailfish number to the sum" },
+    Command { name: "next", args: "", help: "adds the next number of the input to the sum" },
+];
+
+impl Homework {
+    fn add(&mut self, num: Vec<Symbol>) -> String {
+        self.sum = Some(match &self.sum {
+            Some(sum) => reduce(&add(sum, &num)),
+            None => num,
+        });
+        self.added += 1;
+        self.show()
+    }
+}
+
+impl Session for Homework {
+    fn commands(&self) -> &'static [Command] {
+        COMMANDS
+    }
+
+    fn run(&mut self, name: &str, args: &str) -> Result<String, String> {
+        match name {
+            "add" => {
+                let num = parse_number(args).map_err(|e| e.to_string())?;
+                Ok(self.add(num))
+            }
+            "next" => {
+                let num = self.nums.get(self.next).ok_or("all numbers of the input are added")?.clone();
+                self.next += 1;
+                Ok(self.add(num))
+            }
+            _ => Err(format!("unknown command '{}'", name)),
+        }
+    }
+
+    fn show(&self) -> String {
+        match &self.sum {
+            Some(sum) => format!("Sum of {} numbers, magnitude {}:\n{}\n", self.added, magnitude(sum), to_string(sum)),
+            None => String::from("Nothing added yet\n"),
+        }
+    }
+}
+
+/// The puzzle, for the runner.
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<Vec<Symbol>>;
+
+    fn example(&self) -> &'static str {
+        TEST_DATA
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Vec<Vec<Symbol>>, AocError> {
+        parse(lines)
+    }
+
+    fn part_one(&self, nums: &Vec<Vec<Symbol>>) -> Answer {
+        star_one(nums).into()
+    }
+
+    fn part_two(&self, nums: &Vec<Vec<Symbol>>) -> Answer {
+        star_two(nums).into()
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn session(&self, nums: &Vec<Vec<Symbol>>) -> Option<Box<dyn Session>> {
+        Some(Box::new(Homework { nums: nums.clone(), next: 0, sum: None, added: 0 }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::rng::Rng;
+
+    use super::{Symbol, TEST_DATA};
+
+    fn max_depth(num: &[Symbol]) -> u32 {
+        let mut depth = 0;
+        let mut max = 0;
+        for sym in num {
+            match sym {
+                Symbol::LeftParen => {
+                    depth += 1;
+                    max = max.max(depth);
+                }
+                Symbol::RightParen => depth -= 1,
+                _ => (),
+            }
+        }
+        max
+    }
+
+    static TEST_EXPLODE_1: &str = "[[[[[9,8],1],2],3],4]";
+
+    #[test]
+    fn explode_one()
+    {
+        let symbols = super::parse_simple(1, TEST_EXPLODE_1, 5).unwrap();
+        let result = super::explode(&symbols);
+        assert!(result.1);
+        let symbols = result.0;
+        assert_eq!(symbols[4], super::Symbol::Number(0));
+        assert_eq!(symbols[6], super::Symbol::Number(9));
+    }
+
+    static TEST_EXPLODE_2: &str = "[[6,[5,[4,[3,2]]]],1]";
+
+    #[test]
+    fn explode_two()
+    {
+        let symbols = super::parse_simple(1, TEST_EXPLODE_2, 5).unwrap();
+        let result = super::explode(&symbols);
+        assert!(result.1);
+        let symbols = result.0;
+        assert_eq!(symbols[8], super::Symbol::Number(7));
+        assert_eq!(symbols[10], super::Symbol::Number(0));
+        assert_eq!(symbols[15], super::Symbol::Number(3));
+    }
+
+    static TEST_REDUCE: &str = "[[[[4,3],4],4],[7,[[8,4],9]]]
+[1,1]";
+
+    #[test]
+    fn reduce()
+    {
+        let left = super::parse_simple(1, TEST_REDUCE.lines().next().unwrap(), 4).unwrap();
+        let right = super::parse_simple(1, TEST_REDUCE.lines().nth(1).unwrap(), 4).unwrap();
+        let num = super::add(&left, &right);
+        let red = super::reduce(&num);
+        assert_eq!(red[4], super::Symbol::Number(0));
+        assert_eq!(red[14], super::Symbol::Number(7));
+    }
+
+    static TEST_MAGNITUDE: &str = "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]";
+
+    #[test]
+    fn magnitude()
+    {
+        let num = super::parse_simple(1, TEST_MAGNITUDE, 4).unwrap();
+        let mag = super::magnitude(&num);
+        assert_eq!(mag, 3488);
+    }
+
+    #[test]
+    fn parse_error() {
+        let err = super::parse_simple(7, "[[1,2],x]", 4).unwrap_err();
+        assert_eq!(err.to_string(), "7:8: expected '[', ']', ',' or a digit, found 'x'");
+        let err = super::parse_simple(1, "[[1,2]3]", 4).unwrap_err();
+        assert_eq!(err.to_string(), "1:7: expected ',', found '3'");
+        let err = super::parse_simple(1, "[1,2]]", 4).unwrap_err();
+        assert_eq!(err.to_string(), "1:6: expected end of line, found ']'");
+        let err = super::parse_simple(1, "[[1,2],", 4).unwrap_err();
+        assert_eq!(err.to_string(), "1:8: expected '[' or a digit");
+        let err = super::parse_simple(1, "[[[[[1,2],3],4],5],6]", 4).unwrap_err();
+        assert_eq!(err.to_string(), "1:5: expected a regular number, pairs are nested too deep, found '['");
+        assert!(super::parse(&[]).is_err());
+    }
+
+
+    #[test]
+    fn test_star_one() {
+        let lines: Vec<String> = TEST_DATA
+            .lines()
+            .map(|x| x.to_string())
+            .collect();
+
+        let ans = super::star_one(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 4140);
+    }
+
+    #[test]
+    fn reduce_stays_reduced() {
+        common::prop::check(
+            300,
+            |rng, size| (super::random_number(rng, 1, size), super::random_number(rng, 1, size)),
+            |(left, right)| {
+                let red = super::reduce(&super::add(left, right));
+                if max_depth(&red) > 4 {
+                    return Err(format!("pairs nested {} deep", max_depth(&red)));
+                }
+                match red.iter().find(|sym| matches!(sym, Symbol::Number(val) if *val >= 10)) {
+                    Some(sym) => Err(format!("{:?} left after reducing", sym)),
+                    None => Ok(()),
+                }
+            },
+        );
+    }
+
+    #[test]
+    fn generate() {
+        let input = super::generate(&mut Rng::new(18), 20);
+        assert_eq!(input, super::generate(&mut Rng::new(18), 20));
+
+        let lines: Vec<String> = input.lines().map(|x| x.to_string()).collect();
+        let nums = super::parse(&lines).unwrap();
+        assert_eq!(nums.len(), 20);
+        assert_eq!(super::to_string(&nums[0]), lines[0]);
+        assert!(super::star_two(&nums) > 0);
+    }
+
+    #[test]
+    fn session() {
+        use common::Solution;
+
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+        let mut session = super::Day18.session(&super::parse(&lines).unwrap()).unwrap();
+
+        assert_eq!(session.show(), "Nothing added yet\n");
+        session.run("add", "[[[[4,3],4],4],[7,[[8,4],9]]]").unwrap();
+        assert_eq!(
+            session.run("add", "[1,1]").unwrap(),
+            "Sum of 2 numbers, magnitude 1384:\n[[[[0,7],4],[[7,8],[6,0]]],[8,1]]\n"
+        );
+        assert!(session.run("next", "").unwrap().starts_with("Sum of 3 numbers, "));
+        assert_eq!(session.run("add", "[1,x]"), Err(String::from("1:4: expected '[', ']', ',' or a digit, found 'x'")));
+    }
+}
@@ -0,0 +1,14 @@
+#![no_main]
+
+use common::Solution;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let day = day03::Day03::default();
+    let lines = common::input::lossy_lines(data);
+    let answers = day.parse(&lines).ok().map(|input| (day.part_one(&input), day.part_two(&input)));
+    let mut reader = common::LineReader::new(std::io::Cursor::new(lines.join("\n")));
+    if let (Some(answers), Some(Ok(streamed))) = (answers, day.solve_stream(&mut reader)) {
+        assert_eq!(answers, streamed);
+    }
+});
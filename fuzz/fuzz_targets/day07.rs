@@ -0,0 +1,12 @@
+#![no_main]
+
+use common::Solution;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let day = day07::Day07;
+    if let Ok(input) = day.parse(&common::input::lossy_lines(data)) {
+        day.part_one(&input);
+        day.part_two(&input);
+    }
+});
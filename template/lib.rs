@@ -0,0 +1,69 @@
+//! Day NN of Advent of Code 2021.
+
+#![warn(missing_docs)]
+
+use std::vec::Vec;
+
+use common::{Answer, AocError, Solution};
+
+/// The puzzle input.
+pub fn parse(lines: &[String]) -> Result<Vec<String>, AocError> {
+    Ok(lines.to_vec())
+}
+
+/// The answer of part one.
+pub fn star_one(_lines: &[String]) -> usize {
+    0
+}
+
+/// The answer of part two.
+pub fn star_two(_lines: &[String]) -> usize {
+    0
+}
+
+/// The example from the puzzle description
+pub static TEST_DATA: &str = include_str!("../../examples/dayNN/example.txt");
+
+/// The puzzle, for the runner.
+pub struct DayNN;
+
+impl Solution for DayNN {
+    type Input = Vec<String>;
+
+    fn example(&self) -> &'static str {
+        TEST_DATA
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Vec<String>, AocError> {
+        parse(lines)
+    }
+
+    fn part_one(&self, lines: &Vec<String>) -> Answer {
+        star_one(lines).into()
+    }
+
+    fn part_two(&self, lines: &Vec<String>) -> Answer {
+        star_two(lines).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TEST_DATA;
+
+    #[test]
+    fn test_star_one() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let ans = super::star_one(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 0);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let lines: Vec<String> = TEST_DATA.lines().map(|x| x.to_string()).collect();
+
+        let ans = super::star_two(&super::parse(&lines).unwrap());
+        assert_eq!(ans, 0);
+    }
+}